@@ -77,44 +77,762 @@
 //! );
 //! ```
 //!
+//! ## Example: Enum
+//!
+//! Struct variants get a constructor returning a builder of that variant's fields, e.g. `ShapeRect`, converted back with `into()`. Setters of other variants do not exist on it.
+//!
+//! ```rust
+//! use aksr::Builder;
+//!
+//! #[derive(Builder, Debug, PartialEq)]
+//! enum Shape {
+//!     Circle { r: f32 },
+//!     Rect { w: f32, h: f32 },
+//! }
+//!
+//! let rect: Shape = Shape::rect().with_w(3.0).with_h(4.0).into();
+//! assert_eq!(rect, Shape::Rect { w: 3.0, h: 4.0 });
+//! ```
+//!
+//! ```rust,compile_fail
+//! # use aksr::Builder;
+//! # #[derive(Builder, Debug, PartialEq)]
+//! # enum Shape {
+//! #     Circle { r: f32 },
+//! #     Rect { w: f32, h: f32 },
+//! # }
+//! // `w` is not a field of `Shape::Circle`
+//! let circle: Shape = Shape::circle().with_w(1.0).into();
+//! ```
+//!
+//! ## Example: Secrets
+//!
+//! `secret` only changes the accessors: the getter becomes `x_redacted()`, and `x_exposed()` needs `expose`. It does not wipe memory. With the `zeroize` feature, add `zeroize` to the same field for a `zeroize_x()` method and for `take_x_from` to wipe the value it replaces. Nothing is wiped on drop; hold the value in `zeroize::Zeroizing` for that.
+//!
+//! ```rust
+//! use aksr::Builder;
+//!
+//! #[derive(Builder, Default)]
+//! struct Login {
+//!     #[args(secret)]
+//!     password: String,
+//!     #[args(secret, expose)]
+//!     token: String,
+//! }
+//!
+//! let login = Login::default().with_password("hunter2").with_token("abc");
+//! assert_eq!(login.password_redacted(), "***");
+//! assert_eq!(login.token_exposed(), "abc");
+//! ```
+//!
 
 use proc_macro::TokenStream;
 use proc_macro2::{Ident, Span};
 use quote::quote;
 use syn::{
-    parse_macro_input, Data, DataStruct, DeriveInput, Field, GenericArgument, Index, PathArguments,
-    Type,
+    parse_macro_input, punctuated::Punctuated, Attribute, Data, DataEnum, DataStruct, DeriveInput,
+    Field, GenericArgument, GenericParam, ImplItem, Index, ItemImpl, Meta, PathArguments,
+    PathSegment, Token, Type,
 };
 
 mod misc;
-use misc::{Fns, Rules, Tys};
+use misc::{to_camel_case, to_snake_case, Fns, InlineStruct, RangeMode, Rules, StructRules, Tys};
 
 const ARGS: &str = "args";
+const BUILDER: &str = "builder";
 const ALIAS: &str = "alias";
 const GETTER: &str = "getter";
 const SETTER: &str = "setter";
 const SETTER_PREFIX: &str = "setter_prefix";
 const GETTER_PREFIX: &str = "getter_prefix";
 const INC_FOR_VEC: &str = "inc";
+const IS_DEFAULT: &str = "is_default";
+const TRACK_SET: &str = "track_set";
+const VIEW: &str = "view";
+const MERGE_JSON: &str = "merge_json";
+const OK_WRAP: &str = "ok_wrap";
+const TAKE_FROM: &str = "take_from";
+const SECRET: &str = "secret";
+const EXPOSE: &str = "expose";
+const ZEROIZE: &str = "zeroize";
+const FFI: &str = "ffi";
+const CLAMP: &str = "clamp";
+const HANDLE: &str = "handle";
+const RENAMED_FROM: &str = "renamed_from";
+const DEBUG_LOG: &str = "debug_log";
+const INVARIANT: &str = "invariant";
+const DEBUG_INVARIANT: &str = "debug_invariant";
+const READONLY: &str = "readonly";
+const WRITEONLY: &str = "writeonly";
+const NESTED: &str = "nested";
+const LAZY: &str = "lazy";
+const FIELD_ID: &str = "field_id";
+const APPLY: &str = "apply";
+const BUILD: &str = "build";
+const WHEN: &str = "when";
+const VISIBILITY: &str = "visibility";
+const GETTER_VISIBILITY: &str = "getter_visibility";
+const SETTER_VISIBILITY: &str = "setter_visibility";
+const INLINE: &str = "inline";
+const RENAME_ALL: &str = "rename_all";
+const DOCS: &str = "docs";
+const MODE: &str = "mode";
+const MUST_USE: &str = "must_use";
+const DEFAULT_SKIP: &str = "default_skip";
+const ALLOW: &str = "allow";
+const DEFAULT: &str = "default";
+const PATCH: &str = "patch";
+const REQUIRED: &str = "required";
+const VALIDATE: &str = "validate";
+const TRY_INTO: &str = "try_into";
+const FROM_STR: &str = "from_str";
+const RANGE: &str = "range";
+const RANGE_MODE: &str = "range_mode";
+const MAP: &str = "map";
+const MAP_WITH: &str = "map_with";
+const ON_SET: &str = "on_set";
+const TRACE: &str = "trace";
+const INTO: &str = "into";
+const EACH: &str = "each";
+const RESET: &str = "reset";
+const SETTERS: &str = "setters";
+const TOGGLE: &str = "toggle";
+const BOOL_GETTER: &str = "bool_getter";
+const REF_VIEW: &str = "ref_view";
+const VIEW_TRAIT: &str = "view_trait";
+const VIEW_TRAIT_IMPL: &str = "view_trait_impl";
+const IMPL_TRAIT: &str = "impl_trait";
+const PUBLIC_ONLY: &str = "public_only";
 const SETTER_PREFIX_DEFAULT: &str = "with";
+const SETTER_PREFIX_BY_REF: &str = "set";
 const GETTER_PREFIX_DEFAULT: &str = "nth";
 const PRIMITIVE_TYPES: &[&str] = &[
     "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize", "bool",
     "char", "unit", "f32", "f64",
 ];
 
-#[proc_macro_derive(Builder, attributes(args))]
+#[proc_macro_derive(Builder, attributes(args, builder))]
 pub fn derive(x: TokenStream) -> TokenStream {
     let st = parse_macro_input!(x as DeriveInput);
-    let expanded = build_expanded(st);
+    let struct_rules = StructRules::from(st.attrs.as_slice());
+    let expanded = build_expanded(st, struct_rules);
+    TokenStream::from(expanded)
+}
+
+/// Getters half of `#[derive(Builder)]`
+#[proc_macro_derive(Getters, attributes(args, builder))]
+pub fn derive_getters(x: TokenStream) -> TokenStream {
+    let st = parse_macro_input!(x as DeriveInput);
+    let mut struct_rules = StructRules::from(st.attrs.as_slice());
+    struct_rules.skip_setters = true;
+    let expanded = build_expanded(st, struct_rules);
     TokenStream::from(expanded)
 }
 
-fn build_expanded(st: DeriveInput) -> proc_macro2::TokenStream {
-    // generate code
-    let code = match &st.data {
-        Data::Struct(data) => generate_from_struct(data),
+/// Setters half of `#[derive(Builder)]`
+#[proc_macro_derive(Setters, attributes(args, builder))]
+pub fn derive_setters(x: TokenStream) -> TokenStream {
+    let st = parse_macro_input!(x as DeriveInput);
+    let mut struct_rules = StructRules::from(st.attrs.as_slice());
+    struct_rules.skip_getters = true;
+    let expanded = build_expanded(st, struct_rules);
+    TokenStream::from(expanded)
+}
+
+/// Defines a named struct, a `Default` impl built from inline `= value` defaults
+/// and the accessors of `#[derive(Builder)]`, in one block.
+///
+/// ```rust
+/// aksr::builder! {
+///     #[derive(Debug, Clone)]
+///     pub struct Options {
+///         depth: usize = 3,
+///         #[args(alias = "label")]
+///         name: String = "root".into(),
+///         tags: Vec<String>,
+///     }
+/// }
+///
+/// let options = Options::default().with_depth(5);
+/// assert_eq!(options.depth(), 5);
+/// assert_eq!(options.label(), "root");
+/// ```
+#[proc_macro]
+pub fn builder(x: TokenStream) -> TokenStream {
+    let InlineStruct { st, defaults } = parse_macro_input!(x as InlineStruct);
+    let item = strip_args(&st);
+    let Data::Struct(data) = &st.data else {
+        unreachable!()
+    };
+    let defaults = data
+        .fields
+        .iter()
+        .zip(defaults)
+        .map(|(field, default)| default.or(Rules::from(field).default))
+        .collect::<Vec<_>>();
+    let default_impl = generate_default(&item, defaults.clone());
+    let mut struct_rules = StructRules::from(st.attrs.as_slice());
+    struct_rules.skip_default_impl = true;
+    struct_rules.defaults = Some(defaults);
+    let accessors = build_expanded(st, struct_rules);
+
+    TokenStream::from(quote! {
+        #item
+
+        #default_impl
+
+        #accessors
+    })
+}
+
+/// Attribute form of `#[derive(Builder)]`, which may also rewrite the struct:
+/// - strips the `args` & `builder` helper attributes
+/// - implements `Default` from `#[args(default = ...)]` values, plus `new()`, when not derived
+/// - derives `Debug` when missing
+/// - opt out with `default = false` or `debug = false`
+/// - wraps `#[args(lazy)]` fields of type `T` into `Option<T>`, left unset by `Default`
+///
+/// ```rust
+/// #[aksr::builder_attr]
+/// struct Session {
+///     #[args(default = 7)]
+///     id: u64,
+///     #[args(lazy)]
+///     token: String,
+/// }
+///
+/// let session = Session::new();
+/// assert_eq!(session.id(), 7);
+/// assert_eq!(session.token(), None);
+/// assert_eq!(session.with_token("abc").token(), Some("abc"));
+/// ```
+#[proc_macro_attribute]
+pub fn builder_attr(args: TokenStream, x: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args with Punctuated::<Meta, Token![,]>::parse_terminated);
+    let mut st = parse_macro_input!(x as DeriveInput);
+
+    // derives to inject
+    let (mut gen_default, mut gen_debug) = (true, true);
+    for meta in &args {
+        let (ident, value) = match meta {
+            Meta::Path(path) => (path.get_ident(), true),
+            Meta::NameValue(name_value) => (
+                name_value.path.get_ident(),
+                Rules::parse_bool_or_str(&name_value.value),
+            ),
+            Meta::List(_) => continue,
+        };
+        match ident.map(|i| i.to_string()).as_deref() {
+            Some("default") => gen_default = value,
+            Some("debug") => gen_debug = value,
+            _ => panic!(
+                "Builder(aksr): unknown `builder` argument: {}",
+                quote! { #meta }
+            ),
+        }
+    }
+    for attr in st
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("derive"))
+    {
+        if let Ok(paths) =
+            attr.parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)
+        {
+            for path in paths {
+                match path.segments.last().map(|x| x.ident.to_string()).as_deref() {
+                    Some("Default") => gen_default = false,
+                    Some("Debug") => gen_debug = false,
+                    _ => {}
+                }
+            }
+        }
+    }
+    let derives = match gen_debug {
+        true => quote! { #[derive(::core::fmt::Debug)] },
+        false => quote! {},
+    };
+
+    // rewrite fields, collect defaults
+    let mut defaults = Vec::new();
+    match &mut st.data {
+        Data::Struct(data) => {
+            for field in data.fields.iter_mut() {
+                let rules = Rules::from(&*field);
+                if rules.lazy {
+                    let ty = &field.ty;
+                    field.ty = syn::parse_quote! { Option<#ty> };
+                }
+                defaults.push(rules.default);
+            }
+        }
         Data::Enum(_) | Data::Union(_) => panic!("Builder(aksr) can only be derived for struct"),
+    }
+
+    let item = strip_args(&st);
+    let has_required = match &st.data {
+        Data::Struct(data) => data
+            .fields
+            .iter()
+            .any(|field| Rules::from(field).required && !is_option(&field.ty)),
+        _ => false,
+    };
+    let default_impl = match gen_default {
+        true if has_required => generate_default(&item, defaults),
+        true => {
+            let default_impl = generate_default(&item, defaults);
+            let (struct_name, (impl_generics, ty_generics, where_clause)) =
+                (&item.ident, &item.generics.split_for_impl());
+            quote! {
+                #default_impl
+
+                impl #impl_generics #struct_name #ty_generics #where_clause {
+                    pub fn new() -> Self {
+                        ::core::default::Default::default()
+                    }
+                }
+            }
+        }
+        false if defaults.iter().any(Option::is_some) => {
+            panic!("Builder(aksr): `default` values need the `Default` impl of `#[aksr::builder_attr]`, remove `#[derive(Default)]`")
+        }
+        false => quote! {},
+    };
+    let mut struct_rules = StructRules::from(st.attrs.as_slice());
+    struct_rules.skip_default_impl = true;
+    let accessors = build_expanded(st, struct_rules);
+
+    TokenStream::from(quote! {
+        #derives
+        #item
+
+        #default_impl
+
+        #accessors
+    })
+}
+
+/// `Default` impl, fields without a value fall back to their own default
+fn generate_default(
+    st: &DeriveInput,
+    defaults: Vec<Option<syn::Expr>>,
+) -> proc_macro2::TokenStream {
+    let Data::Struct(data) = &st.data else {
+        panic!("Builder(aksr) can only be derived for struct")
+    };
+    let values = data
+        .fields
+        .iter()
+        .zip(defaults)
+        .map(|(field, default)| default_value(field, default))
+        .collect::<Vec<_>>();
+    let value = match &data.fields {
+        syn::Fields::Named(_) => {
+            let names = data.fields.iter().map(|field| &field.ident);
+            quote! { Self { #(#names: #values,)* } }
+        }
+        syn::Fields::Unnamed(_) => quote! { Self(#(#values,)*) },
+        syn::Fields::Unit => quote! { Self },
+    };
+    let (struct_name, (impl_generics, ty_generics, where_clause)) =
+        (&st.ident, &st.generics.split_for_impl());
+
+    quote! {
+        impl #impl_generics ::core::default::Default for #struct_name #ty_generics #where_clause {
+            fn default() -> Self {
+                #value
+            }
+        }
+    }
+}
+
+/// Initial value of a field, from its `default` expression or `Default`
+fn default_value(field: &Field, default: Option<syn::Expr>) -> proc_macro2::TokenStream {
+    match default {
+        // string literals: text of string-like fields, otherwise the expression they hold
+        Some(syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(x),
+            ..
+        })) => {
+            if is_string_like(&field.ty) {
+                quote! { ::core::convert::Into::into(#x) }
+            } else if option_inner(&field.ty).is_some_and(is_string_like) {
+                quote! { Some(::core::convert::Into::into(#x)) }
+            } else {
+                match x.parse::<syn::Expr>() {
+                    Ok(expr) => quote! { #expr },
+                    Err(_) => panic!(
+                        "Builder(aksr): invalid `default` expression: {:?}",
+                        x.value()
+                    ),
+                }
+            }
+        }
+        Some(x) => quote! { #x },
+        None => quote! { ::core::default::Default::default() },
+    }
+}
+
+/// `new()` taking the `#[args(required)]` fields, the others from their defaults;
+/// required `Option<T>` fields start unset and are checked by `build()` instead
+fn generate_new(st: &DeriveInput, data_struct: &DataStruct) -> proc_macro2::TokenStream {
+    let mut params = Vec::new();
+    let mut values = Vec::new();
+    for (idx, field) in data_struct.fields.iter().enumerate() {
+        let rules = Rules::from(field);
+        let field_type = &field.ty;
+        if rules.required && !is_option(field_type) {
+            let name = field
+                .ident
+                .clone()
+                .unwrap_or_else(|| Ident::new(&format!("x{}", idx), Span::call_site()));
+            params.push(quote! { #name: #field_type });
+            values.push(quote! { #name });
+        } else {
+            values.push(default_value(field, rules.default));
+        }
+    }
+    let value = match &data_struct.fields {
+        syn::Fields::Named(_) => {
+            let names = data_struct.fields.iter().map(|field| &field.ident);
+            quote! { Self { #(#names: #values,)* } }
+        }
+        _ => quote! { Self(#(#values,)*) },
+    };
+    let vis = &st.vis;
+
+    quote! {
+        #vis fn new(#(#params),*) -> Self {
+            #value
+        }
+    }
+}
+
+/// `build()` checking every required `Option<T>` field was set, then every `validate`/`range`
+/// field holds, reporting all failures of the first kind at once
+fn generate_build(
+    st: &DeriveInput,
+    data_struct: &DataStruct,
+    struct_rules: &StructRules,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let struct_name = &st.ident;
+    let error_name = Ident::new(&format!("{}BuildError", struct_name), Span::call_site());
+    let vis = &st.vis;
+    let mut missing = Vec::new();
+    let mut checks = Vec::new();
+    for (idx, field) in data_struct.fields.iter().enumerate() {
+        let rules = Rules::from(field);
+        let field_index = Index::from(idx);
+        let field_access = field
+            .ident
+            .as_ref()
+            .map_or_else(|| quote! { #field_index }, |name| quote! { #name });
+        let name = rules.base_name(field, idx);
+        if rules.required && is_option(&field.ty) {
+            missing.push(quote! {
+                if self.#field_access.is_none() {
+                    fields.push(#name);
+                }
+            });
+        }
+        if let Some(validate) = &rules.validate {
+            checks.push(quote! {
+                if let Err(e) = #validate(&self.#field_access) {
+                    invalid.push((#name, e));
+                }
+            });
+        }
+        if let Some((range, text)) = &rules.range {
+            let reason = format!("out of range {}", text);
+            let contains = match is_option(&field.ty) {
+                true => {
+                    quote! { self.#field_access.as_ref().map_or(true, |v| (#range).contains(v)) }
+                }
+                false => quote! { (#range).contains(&self.#field_access) },
+            };
+            checks.push(quote! {
+                if !#contains {
+                    invalid.push((#name, #reason.to_string()));
+                }
+            });
+        }
+    }
+
+    let build_fn = quote! {
+        pub fn build(self) -> Result<Self, #error_name> {
+            let mut fields = Vec::new();
+            #(#missing)*
+            if !fields.is_empty() {
+                return Err(#error_name::MissingField(fields));
+            }
+            let mut invalid = Vec::new();
+            #(#checks)*
+            if !invalid.is_empty() {
+                return Err(#error_name::Invalid(invalid));
+            }
+            Ok(self)
+        }
+    };
+    let struct_name_str = struct_name.to_string();
+    let doc = |text: &str| (!struct_rules.skip_docs).then(|| quote! { #[doc = #text] });
+    let (doc_missing, doc_invalid) = (
+        doc(" required fields left unset"),
+        doc(" fields failing `validate` or `range`, with the reason"),
+    );
+    let items = quote! {
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        #vis enum #error_name {
+            #doc_missing
+            MissingField(Vec<&'static str>),
+            #doc_invalid
+            Invalid(Vec<(&'static str, String)>),
+        }
+
+        impl ::core::fmt::Display for #error_name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match self {
+                    Self::MissingField(fields) => {
+                        write!(f, "missing required fields of `{}`: {}", #struct_name_str, fields.join(", "))
+                    }
+                    Self::Invalid(invalid) => {
+                        let reasons = invalid
+                            .iter()
+                            .map(|(field, reason)| format!("`{}`: {}", field, reason))
+                            .collect::<Vec<_>>();
+                        write!(f, "invalid fields of `{}`: {}", #struct_name_str, reasons.join("; "))
+                    }
+                }
+            }
+        }
+
+        impl ::std::error::Error for #error_name {}
+    };
+
+    (build_fn, items)
+}
+
+/// `Option<T>`
+fn is_option(ty: &Type) -> bool {
+    matches!(ty, Type::Path(x) if x.path.segments.last().is_some_and(|x| x.ident == "Option"))
+}
+
+/// `T` of `Option<T>`
+fn option_inner(ty: &Type) -> Option<&Type> {
+    match ty {
+        Type::Path(x) if is_option(ty) => match nth_generic_arg(x.path.segments.last()?, 0)? {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// `String`, `&str`, `Box<str>` and friends, built from string literals
+fn is_string_like(ty: &Type) -> bool {
+    let is_str = |ty: &Type| matches!(ty, Type::Path(x) if x.path.is_ident("str"));
+    match ty {
+        Type::Reference(x) => is_str(&x.elem),
+        Type::Path(x) => x.path.segments.last().is_some_and(|x| {
+            match x.ident.to_string().as_str() {
+                "String" | "Cow" | "PathBuf" | "OsString" => true,
+                "Box" | "Arc" | "Rc" => {
+                    matches!(nth_generic_arg(x, 0), Some(GenericArgument::Type(ty)) if is_str(ty))
+                }
+                _ => false,
+            }
+        }),
+        _ => false,
+    }
+}
+
+/// `dyn Trait`, `str` & `[T]`, which can not be taken by value
+fn is_unsized(ty: &Type) -> bool {
+    match ty {
+        Type::TraitObject(_) | Type::Slice(_) => true,
+        Type::Path(x) => x.path.is_ident("str"),
+        _ => false,
+    }
+}
+
+/// Primitive of `NonZeroU32`, `NonZero<u32>` and friends
+fn nonzero_raw(ty: &Type) -> Option<proc_macro2::TokenStream> {
+    let Type::Path(x) = ty else {
+        return None;
+    };
+    let last_segment = x.path.segments.last()?;
+    let ident = last_segment.ident.to_string();
+    match ident.strip_prefix("NonZero")? {
+        "" => match nth_generic_arg(last_segment, 0)? {
+            GenericArgument::Type(ty) => Some(quote! { #ty }),
+            _ => None,
+        },
+        raw => {
+            let raw = raw.to_lowercase();
+            PRIMITIVE_TYPES.contains(&raw.as_str()).then(|| {
+                let raw = Ident::new(&raw, Span::call_site());
+                quote! { #raw }
+            })
+        }
+    }
+}
+
+/// `args` & `builder` are only helper attributes of the derive, strip them from emitted items
+fn strip_args(st: &DeriveInput) -> DeriveInput {
+    let mut item = st.clone();
+    item.attrs
+        .retain(|attr| !attr.path().is_ident(ARGS) && !attr.path().is_ident(BUILDER));
+    if let Data::Struct(data) = &mut item.data {
+        for field in data.fields.iter_mut() {
+            field.attrs.retain(|attr| !attr.path().is_ident(ARGS));
+        }
+    }
+
+    item
+}
+
+fn build_expanded(st: DeriveInput, mut struct_rules: StructRules) -> proc_macro2::TokenStream {
+    // generate code: methods and items outside the impl block,
+    // struct level extras follow the half they belong to
+    let (getters, setters) = (!struct_rules.skip_getters, !struct_rules.skip_setters);
+    let (code, items) = match &st.data {
+        Data::Struct(data) => {
+            let is_set = generate_track_set(data, &mut struct_rules);
+            // struct level helpers take the visibility of their half, as the accessors do
+            let with_struct_vis = |code, is_setter| match struct_rules.vis(is_setter) {
+                Some(vis) => with_vis(code, vis),
+                None => code,
+            };
+            let mut code = quote! {};
+            let mut items = quote! {};
+            if getters {
+                code.extend(with_struct_vis(is_set, false));
+            }
+            let defaults = struct_rules.defaults.clone().unwrap_or_else(|| {
+                data.fields
+                    .iter()
+                    .map(|field| Rules::from(field).default)
+                    .collect()
+            });
+            // construction belongs to the setters half, `Getters` + `Setters` emit it once
+            if !struct_rules.skip_default_impl && setters && defaults.iter().any(Option::is_some) {
+                items.extend(generate_default(&st, defaults.clone()));
+            }
+            let required = data
+                .fields
+                .iter()
+                .filter(|field| Rules::from(*field).required)
+                .collect::<Vec<_>>();
+            if setters && required.iter().any(|field| !is_option(&field.ty)) {
+                code.extend(with_struct_vis(generate_new(&st, data), true));
+            }
+            if setters
+                && (struct_rules.gen_build || required.iter().any(|field| is_option(&field.ty)))
+            {
+                let (build_fn, error_struct) = generate_build(&st, data, &struct_rules);
+                code.extend(with_struct_vis(build_fn, true));
+                items.extend(error_struct);
+            }
+            let mut accessors = generate_from_struct(data, &struct_rules);
+            if getters {
+                accessors.extend(generate_nested(&struct_rules));
+            }
+            // struct level helpers step aside for generated accessors of the same name
+            let taken = accessors.clone();
+            if let Some((trait_path, define)) = &struct_rules.view_trait {
+                items.extend(generate_view_trait(&st, &accessors, trait_path, *define));
+            }
+            match &struct_rules.impl_trait {
+                Some(trait_path) => {
+                    // `with_x_if` only delegates, keep it out of the user trait
+                    let (accessors, conditional) = split_conditional(accessors);
+                    code.extend(conditional);
+                    // trait methods take the visibility of the trait
+                    let accessors = with_vis(accessors, &syn::Visibility::Inherited);
+                    let (impl_generics, ty_generics, where_clause) = st.generics.split_for_impl();
+                    let struct_name = &st.ident;
+                    items.extend(quote! {
+                        impl #impl_generics #trait_path for #struct_name #ty_generics #where_clause {
+                            #accessors
+                        }
+                    });
+                }
+                None => code.extend(accessors),
+            }
+            if struct_rules.gen_is_default && getters {
+                code.extend(with_struct_vis(generate_is_default(data, &defaults), false));
+            }
+            if struct_rules.gen_view && getters {
+                let (view_fn, view_struct) = generate_view(&st, data, "View", "view");
+                code.extend(with_struct_vis(view_fn, false));
+                items.extend(view_struct);
+            }
+            if struct_rules.gen_ref_view && getters {
+                let (view_fn, view_struct) = generate_view(&st, data, "Ref", "as_ref_view");
+                code.extend(with_struct_vis(view_fn, false));
+                items.extend(view_struct);
+            }
+            if !struct_rules.skip_apply && setters && !defines_fn(&taken, "apply") {
+                let apply_fn = quote! {
+                    pub fn apply(self, f: impl FnOnce(Self) -> Self) -> Self {
+                        f(self)
+                    }
+                };
+                code.extend(with_struct_vis(apply_fn, true));
+            }
+            if !struct_rules.skip_when && setters && !defines_fn(&taken, "when") {
+                let when_fn = quote! {
+                    pub fn when(self, cond: bool, f: impl FnOnce(Self) -> Self) -> Self {
+                        if cond {
+                            f(self)
+                        } else {
+                            self
+                        }
+                    }
+                };
+                code.extend(with_struct_vis(when_fn, true));
+            }
+            if struct_rules.gen_field_id && getters {
+                let (get_fn, field_id_enums) = generate_field_id(&st, data);
+                code.extend(with_struct_vis(get_fn, false));
+                items.extend(field_id_enums);
+            }
+            if struct_rules.gen_ffi && getters {
+                items.extend(generate_ffi(&st, data, &struct_rules));
+            }
+            if (struct_rules.gen_patch || struct_rules.gen_merge_json) && setters {
+                if struct_rules.gen_merge_json && !cfg!(feature = "serde") {
+                    panic!("Builder(aksr): `merge_json` requires the `serde` feature of aksr");
+                }
+                let (patch_name, patch_struct) = generate_patch(&st, data, &struct_rules);
+                items.extend(patch_struct);
+                let (_, ty_generics, _) = st.generics.split_for_impl();
+                if struct_rules.gen_merge_json {
+                    let merge_fn = quote! {
+                        pub fn merge_json(&mut self, s: &str) -> Result<(), ::serde_json::Error>
+                        where
+                            #patch_name #ty_generics: ::serde::de::DeserializeOwned,
+                        {
+                            let patch: #patch_name #ty_generics = ::serde_json::from_str(s)?;
+                            patch.apply(self);
+                            Ok(())
+                        }
+                    };
+                    code.extend(with_struct_vis(merge_fn, true));
+                }
+            }
+            (code, items)
+        }
+        Data::Enum(data) => {
+            let mut code = generate_from_enum(data);
+            let mut items = quote! {};
+            if setters {
+                let (ctors, builders) = generate_variant_builders(&st, data);
+                code.extend(ctors);
+                items.extend(builders);
+            }
+            (code, items)
+        }
+        Data::Union(_) => panic!("Builder(aksr) can only be derived for struct and enum"),
     };
 
     // attrs
@@ -126,520 +844,2709 @@ fn build_expanded(st: DeriveInput) -> proc_macro2::TokenStream {
         impl #impl_generics #struct_name #ty_generics #where_clause {
             #code
         }
+
+        #items
+    }
+}
+
+fn generate_from_enum(data_enum: &DataEnum) -> proc_macro2::TokenStream {
+    // is_x(), as_x() -> Option<&..> & into_x() -> Option<..> for every variant
+    let mut codes = quote! {};
+    for variant in &data_enum.variants {
+        let ident = &variant.ident;
+        let name = to_snake_case(&ident.to_string());
+        let is_name = Ident::new(&format!("is_{}", name), Span::call_site());
+        let as_name = Ident::new(&format!("as_{}", name), Span::call_site());
+        let into_name = Ident::new(&format!("into_{}", name), Span::call_site());
+        let bindings = (0..variant.fields.len())
+            .map(|idx| Ident::new(&format!("x{}", idx), Span::call_site()))
+            .collect::<Vec<_>>();
+        let types = variant
+            .fields
+            .iter()
+            .map(|field| &field.ty)
+            .collect::<Vec<_>>();
+        let pattern = match &variant.fields {
+            syn::Fields::Named(fields) => {
+                let names = fields.named.iter().map(|field| &field.ident);
+                quote! { Self::#ident { #(#names: #bindings),* } }
+            }
+            syn::Fields::Unnamed(_) => quote! { Self::#ident(#(#bindings),*) },
+            syn::Fields::Unit => quote! { Self::#ident },
+        };
+        let pattern_any = match &variant.fields {
+            syn::Fields::Named(_) => quote! { Self::#ident { .. } },
+            syn::Fields::Unnamed(_) => quote! { Self::#ident(..) },
+            syn::Fields::Unit => quote! { Self::#ident },
+        };
+
+        codes.extend(quote! {
+            pub fn #is_name(&self) -> bool {
+                matches!(self, #pattern_any)
+            }
+        });
+
+        // a single field is returned as is, several as a tuple
+        let (ref_ty, ty, value) = match types.as_slice() {
+            [] => continue,
+            [ty] => (quote! { &#ty }, quote! { #ty }, quote! { #(#bindings)* }),
+            _ => (
+                quote! { (#(&#types),*) },
+                quote! { (#(#types),*) },
+                quote! { (#(#bindings),*) },
+            ),
+        };
+        codes.extend(quote! {
+            pub fn #as_name(&self) -> Option<#ref_ty> {
+                match self {
+                    #pattern => Some(#value),
+                    #[allow(unreachable_patterns)]
+                    _ => None,
+                }
+            }
+
+            pub fn #into_name(self) -> Option<#ty> {
+                match self {
+                    #pattern => Some(#value),
+                    #[allow(unreachable_patterns)]
+                    _ => None,
+                }
+            }
+        });
+    }
+
+    codes
+}
+
+fn generate_variant_builders(
+    st: &DeriveInput,
+    data_enum: &DataEnum,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    // struct variants: a constructor returning a builder struct of the variant fields, so only
+    // setters of that variant exist, and `From<builder>` back into the enum
+    let mut codes = quote! {};
+    let mut items = quote! {};
+    let enum_name = &st.ident;
+    let (impl_generics, ty_generics, where_clause) = st.generics.split_for_impl();
+
+    // generic params may be unused by a variant, keep them alive with a marker field
+    let marker = (!st.generics.params.is_empty()).then(|| {
+        let params = st.generics.params.iter().filter_map(|param| match param {
+            GenericParam::Lifetime(x) => {
+                let lifetime = &x.lifetime;
+                Some(quote! { &#lifetime () })
+            }
+            GenericParam::Type(x) => {
+                let ident = &x.ident;
+                Some(quote! { fn() -> #ident })
+            }
+            GenericParam::Const(_) => None,
+        });
+        quote! { ::core::marker::PhantomData<(#(#params,)*)> }
+    });
+
+    for variant in &data_enum.variants {
+        let syn::Fields::Named(named) = &variant.fields else {
+            continue;
+        };
+        let ident = &variant.ident;
+        let ctor_name = Ident::new(&to_snake_case(&ident.to_string()), Span::call_site());
+        let builder_name = Ident::new(&format!("{}{}", enum_name, ident), Span::call_site());
+        let names = named
+            .named
+            .iter()
+            .map(|field| &field.ident)
+            .collect::<Vec<_>>();
+        let values = named
+            .named
+            .iter()
+            .map(|field| default_value(field, Rules::from(field).default));
+        let (marker_field, marker_value) = match &marker {
+            Some(marker) => (
+                quote! {
+                    #[args(getter = false, setter = false)]
+                    __aksr: #marker,
+                },
+                quote! { __aksr: ::core::marker::PhantomData, },
+            ),
+            None => (quote! {}, quote! {}),
+        };
+        // serde & co. attributes belong to the enum, the builder keeps docs & cfg only
+        let fields = named.named.iter().map(|field| {
+            let mut field = field.clone();
+            field.attrs.retain(|attr| {
+                ["doc", "cfg", ARGS, BUILDER]
+                    .iter()
+                    .any(|x| attr.path().is_ident(x))
+            });
+            field
+        });
+        let vis = &st.vis;
+        let generics = &st.generics;
+        let builder: DeriveInput = syn::parse_quote! {
+            #vis struct #builder_name #generics #where_clause {
+                #(#fields,)*
+                #marker_field
+            }
+        };
+
+        codes.extend(quote! {
+            pub fn #ctor_name() -> #builder_name #ty_generics {
+                #builder_name { #(#names: #values,)* #marker_value }
+            }
+        });
+
+        // enum level `#[args(...)]` apply to every builder, except checks on the enum itself
+        let mut struct_rules = StructRules::from(st.attrs.as_slice());
+        struct_rules.invariant = None;
+        let doc = (!struct_rules.skip_docs).then(|| {
+            quote! {
+                #[doc = concat!("Builder of [`", stringify!(#enum_name), "::", stringify!(#ident), "`]")]
+            }
+        });
+        let item = strip_args(&builder);
+        let accessors = build_expanded(builder, struct_rules);
+        items.extend(quote! {
+            #doc
+            #item
+
+            #accessors
+
+            impl #impl_generics ::core::convert::From<#builder_name #ty_generics>
+                for #enum_name #ty_generics #where_clause
+            {
+                fn from(x: #builder_name #ty_generics) -> Self {
+                    Self::#ident { #(#names: x.#names,)* }
+                }
+            }
+        });
     }
+
+    (codes, items)
+}
+
+fn generate_nested(struct_rules: &StructRules) -> proc_macro2::TokenStream {
+    // pass-through getters into nested fields, named after the last path segment
+    let mut codes = quote! {};
+    for (path, ty) in &struct_rules.nested {
+        let getter_name = match path.last() {
+            Some(syn::Member::Named(ident)) => ident.clone(),
+            _ => panic!("Builder(aksr): `nested` path must end with a named field"),
+        };
+        let field: Field = syn::parse_quote! { #getter_name: #ty };
+        let field_access = quote! { #(#path).* };
+        let Some((ret, body)) = fns_from_field(&field, &Rules::default())
+            .into_iter()
+            .find_map(|(fn_type, arg)| match fn_type {
+                Fns::Getter(x) => getter_parts(&x, ty, arg, &field_access, &quote! {}),
+                Fns::Setter(_) => None,
+            })
+        else {
+            panic!(
+                "Builder(aksr): unsupported `nested` getter type: {}",
+                quote! { #ty }
+            );
+        };
+        let code = quote! {
+            pub fn #getter_name(&self) -> #ret {
+                #body
+            }
+        };
+        codes.extend(match struct_rules.vis(false) {
+            Some(vis) => with_vis(code, vis),
+            None => code,
+        });
+    }
+
+    codes
 }
 
-fn generate_from_struct(data_struct: &DataStruct) -> proc_macro2::TokenStream {
+fn generate_from_struct(
+    data_struct: &DataStruct,
+    struct_rules: &StructRules,
+) -> proc_macro2::TokenStream {
     // code container
     let mut codes = quote! {};
 
     // traverse
     for (idx, field) in data_struct.fields.iter().enumerate() {
         // build rules from field
-        let rules = Rules::from(field);
+        let rules = Rules::new(field, struct_rules);
+
+        // the bitset field of `track_set` has no accessors
+        if rules.track_set {
+            continue;
+        }
 
         // generate code based on field
-        match &field.ty {
-            Type::Path(type_path) => {
-                if let Some(last_segment) = type_path.path.segments.last() {
-                    match last_segment.ident.to_string().as_str() {
-                        "String" => {
-                            generate(
-                                field,
-                                &rules,
-                                idx,
-                                None,
-                                &mut codes,
-                                Fns::Setter(Tys::String),
-                            );
-                            generate(
-                                field,
-                                &rules,
-                                idx,
-                                None,
-                                &mut codes,
-                                Fns::Getter(Tys::String),
-                            );
-                        }
+        let mut field_codes = quote! {};
+        for (fn_type, arg) in fns_from_field(field, &rules) {
+            generate(
+                field,
+                &rules,
+                idx,
+                struct_rules,
+                arg,
+                &mut field_codes,
+                fn_type,
+            );
+        }
 
-                        "Vec" => {
-                            // Vec<T> -> &[T]
-                            if let PathArguments::AngleBracketed(args) = &last_segment.arguments {
-                                if let Some(arg) = args.args.first() {
-                                    if let GenericArgument::Type(ty) = arg {
-                                        if let Type::Path(type_path) = &ty {
-                                            if let Some(last_segment) =
-                                                type_path.path.segments.last()
-                                            {
-                                                let ident = &last_segment.ident;
-
-                                                // Vec<String> -> &[&str]
-                                                if ident == "String" {
-                                                    generate(
-                                                        field,
-                                                        &rules,
-                                                        idx,
-                                                        None,
-                                                        &mut codes,
-                                                        Fns::Setter(Tys::VecString),
-                                                    );
-
-                                                    // increment ver
-                                                    generate(
-                                                        field,
-                                                        &rules,
-                                                        idx,
-                                                        None,
-                                                        &mut codes,
-                                                        Fns::Setter(Tys::VecStringInc),
-                                                    );
-                                                } else {
-                                                    // setters
-                                                    generate(
-                                                        field,
-                                                        &rules,
-                                                        idx,
-                                                        Some(arg),
-                                                        &mut codes,
-                                                        Fns::Setter(Tys::Vec),
-                                                    );
-
-                                                    // setters inc
-                                                    generate(
-                                                        field,
-                                                        &rules,
-                                                        idx,
-                                                        Some(arg),
-                                                        &mut codes,
-                                                        Fns::Setter(Tys::VecInc),
-                                                    );
-                                                }
+        // move helpers, any type
+        if rules.take_from {
+            let fn_type = Fns::Setter(Tys::TakeFrom);
+            generate(
+                field,
+                &rules,
+                idx,
+                struct_rules,
+                None,
+                &mut field_codes,
+                fn_type,
+            );
+        }
 
-                                                // getters: Vec<T> -> &[T]
-                                                generate(
-                                                    field,
-                                                    &rules,
-                                                    idx,
-                                                    Some(arg),
-                                                    &mut codes,
-                                                    Fns::Getter(Tys::Vec),
-                                                );
-                                            }
-                                        } else {
-                                            // Vec<T> -> &[T]
-                                            // setters
-                                            generate(
-                                                field,
-                                                &rules,
-                                                idx,
-                                                Some(arg),
-                                                &mut codes,
-                                                Fns::Setter(Tys::Vec),
-                                            );
-
-                                            // setters inc
-                                            generate(
-                                                field,
-                                                &rules,
-                                                idx,
-                                                Some(arg),
-                                                &mut codes,
-                                                Fns::Setter(Tys::VecInc),
-                                            );
-                                            // getters: Vec<T> -> &[T]
-                                            generate(
-                                                field,
-                                                &rules,
-                                                idx,
-                                                Some(arg),
-                                                &mut codes,
-                                                Fns::Getter(Tys::Vec),
-                                            );
-                                        }
-                                    }
-                                }
-                            }
-                        }
+        // cheap clones of shared pointers
+        if rules.handle {
+            let is_shared = matches!(
+                &field.ty,
+                Type::Path(x) if x.path.segments.last().is_some_and(|x| x.ident == "Arc" || x.ident == "Rc")
+            );
+            if !is_shared {
+                panic!("Builder(aksr): `handle` requires an `Arc<T>` or `Rc<T>` field");
+            }
+            let fn_type = Fns::Getter(Tys::Handle);
+            generate(
+                field,
+                &rules,
+                idx,
+                struct_rules,
+                None,
+                &mut field_codes,
+                fn_type,
+            );
+        }
 
-                        "Option" => {
-                            // Option<T>
-                            // - T => String => &str
-                            // - T => Vec<U> => &[U]
-                            //   - U => String => &str
-                            if let PathArguments::AngleBracketed(args) = &last_segment.arguments {
-                                if let Some(arg) = &args.args.first() {
-                                    if let GenericArgument::Type(ty) = arg {
-                                        if let Type::Path(type_path) = &ty {
-                                            if let Some(last_segment) =
-                                                type_path.path.segments.last()
-                                            {
-                                                let ident = &last_segment.ident;
-                                                // T => Vec<U> => &[U]
-                                                if ident == "Vec" {
-                                                    if let PathArguments::AngleBracketed(args) =
-                                                        &last_segment.arguments
-                                                    {
-                                                        // U
-                                                        if let Some(arg) = args.args.first() {
-                                                            if let GenericArgument::Type(
-                                                                Type::Path(type_path),
-                                                            ) = arg
-                                                            {
-                                                                if let Some(last_segment) =
-                                                                    type_path.path.segments.last()
-                                                                {
-                                                                    // U => String => &str
-                                                                    // Option<Vec<String>> -> Option<&[&str]>
-                                                                    if last_segment.ident
-                                                                        == "String"
-                                                                    {
-                                                                        generate(
-                                                                            field,
-                                                                            &rules,
-                                                                            idx,
-                                                                            None,
-                                                                            &mut codes,
-                                                                            Fns::Setter(Tys::OptionVecString),
-                                                                        );
-                                                                    } else {
-                                                                        generate(
-                                                                            field,
-                                                                            &rules,
-                                                                            idx,
-                                                                            Some(arg),
-                                                                            &mut codes,
-                                                                            Fns::Setter(
-                                                                                Tys::OptionVec,
-                                                                            ),
-                                                                        );
-                                                                    }
-                                                                }
-                                                            } else {
-                                                                generate(
-                                                                    field,
-                                                                    &rules,
-                                                                    idx,
-                                                                    Some(arg),
-                                                                    &mut codes,
-                                                                    Fns::Setter(Tys::OptionVec),
-                                                                );
-                                                            }
+        // fallible setter of validated fields
+        if rules.validate.is_some() {
+            let fn_type = Fns::Setter(Tys::Validate);
+            generate(
+                field,
+                &rules,
+                idx,
+                struct_rules,
+                None,
+                &mut field_codes,
+                fn_type,
+            );
+        }
 
-                                                            // getters: Option<Vec<T>> -> Option<&[T]>
-                                                            generate(
-                                                                field,
-                                                                &rules,
-                                                                idx,
-                                                                Some(arg),
-                                                                &mut codes,
-                                                                Fns::Getter(Tys::OptionVec),
-                                                            );
-                                                        }
-                                                    }
-                                                } else if ident == "String" {
-                                                    // T => String => &str
-                                                    generate(
-                                                        field,
-                                                        &rules,
-                                                        idx,
-                                                        Some(arg),
-                                                        &mut codes,
-                                                        Fns::Setter(Tys::OptionString),
-                                                    );
-
-                                                    // getters: Option<String> -> Option<&str>
-                                                    generate(
-                                                        field,
-                                                        &rules,
-                                                        idx,
-                                                        Some(arg),
-                                                        &mut codes,
-                                                        Fns::Getter(Tys::OptionString),
-                                                    );
-                                                } else {
-                                                    // T => T
-                                                    generate(
-                                                        field,
-                                                        &rules,
-                                                        idx,
-                                                        Some(arg),
-                                                        &mut codes,
-                                                        Fns::Setter(Tys::Option),
-                                                    );
-
-                                                    if PRIMITIVE_TYPES
-                                                        .contains(&ident.to_string().as_str())
-                                                    {
-                                                        // getters: Option<T> -> Option<T>
-                                                        generate(
-                                                            field,
-                                                            &rules,
-                                                            idx,
-                                                            Some(arg),
-                                                            &mut codes,
-                                                            Fns::Getter(Tys::Option),
-                                                        );
-                                                    } else {
-                                                        // getters: Option<T> -> Option<&T>
-                                                        // Option<Box<T>>, Option<Option<T>>
-                                                        generate(
-                                                            field,
-                                                            &rules,
-                                                            idx,
-                                                            Some(arg),
-                                                            &mut codes,
-                                                            Fns::Getter(Tys::OptionAsRef),
-                                                        );
-                                                    }
-                                                }
-                                            }
-                                        } else {
-                                            //  others: Option<(u8, i8)>, Option<&'a str>,
-                                            if let PathArguments::AngleBracketed(args) =
-                                                &last_segment.arguments
-                                            {
-                                                if let Some(arg) = args.args.first() {
-                                                    // setters
-                                                    generate(
-                                                        field,
-                                                        &rules,
-                                                        idx,
-                                                        Some(arg),
-                                                        &mut codes,
-                                                        Fns::Setter(Tys::Option),
-                                                    );
-
-                                                    // getters
-                                                    if let GenericArgument::Type(ty) = arg {
-                                                        match ty {
-                                                            Type::Reference(_) => {
-                                                                // getters: Option<T> -> Option<T>
-                                                                // Option<&'a str>
-                                                                generate(
-                                                                    field,
-                                                                    &rules,
-                                                                    idx,
-                                                                    Some(arg),
-                                                                    &mut codes,
-                                                                    Fns::Getter(Tys::Option),
-                                                                );
-                                                            }
-                                                            _ => {
-                                                                // getters: Option<T> -> Option<&T>
-                                                                // Option<(u8, i8)>
-                                                                generate(
-                                                                    field,
-                                                                    &rules,
-                                                                    idx,
-                                                                    Some(arg),
-                                                                    &mut codes,
-                                                                    Fns::Getter(Tys::OptionAsRef),
-                                                                );
-                                                            }
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                            }
+        // fallible conversion from wider inputs
+        if rules.try_into {
+            if rules.validate.is_some() {
+                panic!("Builder(aksr): `try_into` and `validate` both generate `try_` setters, use one of them");
+            }
+            let fn_type = Fns::Setter(Tys::TryInto);
+            generate(
+                field,
+                &rules,
+                idx,
+                struct_rules,
+                None,
+                &mut field_codes,
+                fn_type,
+            );
+        }
+
+        // parse from a string, e.g. CLI args or env vars
+        if rules.from_str {
+            // `Option<T>` parses `T`
+            let arg = match &field.ty {
+                Type::Path(x) if is_option(&field.ty) => {
+                    nth_generic_arg(x.path.segments.last().unwrap(), 0)
+                }
+                _ => None,
+            };
+            let fn_type = Fns::Setter(Tys::FromStr);
+            generate(
+                field,
+                &rules,
+                idx,
+                struct_rules,
+                arg,
+                &mut field_codes,
+                fn_type,
+            );
+        }
+
+        // closure transform of the current value
+        if rules.map_fn {
+            let fn_type = Fns::Setter(Tys::MapFn);
+            generate(
+                field,
+                &rules,
+                idx,
+                struct_rules,
+                None,
+                &mut field_codes,
+                fn_type,
+            );
+        }
+
+        // `enable_x()` & `disable_x()` of flags
+        if rules.toggle {
+            if !matches!(&field.ty, Type::Path(x) if x.path.is_ident("bool")) {
+                panic!("Builder(aksr): `toggle` is only supported on `bool` fields");
+            }
+            let fn_type = Fns::Setter(Tys::Toggle);
+            generate(
+                field,
+                &rules,
+                idx,
+                struct_rules,
+                None,
+                &mut field_codes,
+                fn_type,
+            );
+        }
+
+        // restore the default value
+        if rules.reset {
+            let fn_type = Fns::Setter(Tys::Reset);
+            generate(
+                field,
+                &rules,
+                idx,
+                struct_rules,
+                None,
+                &mut field_codes,
+                fn_type,
+            );
+        }
+
+        // range checks
+        if let Some((range, _)) = &rules.range {
+            if rules.clamp.is_some() {
+                panic!("Builder(aksr): `range` and `clamp` can not be combined");
+            }
+            match rules.range_mode {
+                RangeMode::Clamp
+                    if range.end.is_some()
+                        && !matches!(range.limits, syn::RangeLimits::Closed(_)) =>
+                {
+                    panic!("Builder(aksr): clamping `range` must be inclusive, e.g. \"0..=100\"")
+                }
+                RangeMode::Try => {
+                    if rules.validate.is_some() || rules.try_into {
+                        panic!("Builder(aksr): `range_mode = \"try\"` conflicts with other `try_` setters");
+                    }
+                    let fn_type = Fns::Setter(Tys::Range);
+                    generate(
+                        field,
+                        &rules,
+                        idx,
+                        struct_rules,
+                        None,
+                        &mut field_codes,
+                        fn_type,
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        // wipe secrets in place
+        if rules.zeroize {
+            if !cfg!(feature = "zeroize") {
+                panic!("Builder(aksr): `zeroize` requires the `zeroize` feature of aksr");
+            }
+            let fn_type = Fns::Setter(Tys::Zeroize);
+            generate(
+                field,
+                &rules,
+                idx,
+                struct_rules,
+                None,
+                &mut field_codes,
+                fn_type,
+            );
+        }
+
+        // deprecated shim under the old method name
+        if let Some(old_name) = &rules.renamed_from {
+            let (setter_name, getter_name) = rules.generate_setter_getter_names(field, idx);
+            let target = if old_name.starts_with(&format!("{}_", rules.prefix_setter)) {
+                setter_name
+            } else {
+                getter_name
+            };
+            field_codes.extend(generate_renamed(&field_codes, &target, old_name));
+        }
+
+        codes.extend(field_codes);
+    }
+
+    // token stream
+    quote! {
+        #codes
+    }
+}
+
+/// Thin `#[deprecated]` forwarder named `old_name` for the generated method `target`
+fn generate_renamed(
+    code: &proc_macro2::TokenStream,
+    target: &Ident,
+    old_name: &str,
+) -> proc_macro2::TokenStream {
+    let item: ItemImpl = syn::parse_quote! { impl __ { #code } };
+    let Some(mut f) = item.items.into_iter().find_map(|x| match x {
+        ImplItem::Fn(f) if f.sig.ident == *target => Some(f),
+        _ => None,
+    }) else {
+        panic!(
+            "Builder(aksr): `renamed_from = {:?}` has no generated method `{}` to forward to",
+            old_name, target
+        );
+    };
+    let args = f.sig.inputs.iter().filter_map(|x| match x {
+        syn::FnArg::Typed(x) => Some(&x.pat),
+        syn::FnArg::Receiver(_) => None,
+    });
+    let body = quote! {{ self.#target(#(#args),*) }};
+    let note = format!("renamed to `{}`", target);
+    f.block = syn::parse2(body).expect("forwarding body");
+    f.sig.ident = Ident::new(old_name, Span::call_site());
+    // `mut self` is not needed when only forwarding
+    if let Some(syn::FnArg::Receiver(receiver)) = f.sig.inputs.first_mut() {
+        if receiver.reference.is_none() {
+            receiver.mutability = None;
+        }
+    }
+    f.attrs
+        .push(syn::parse_quote! { #[deprecated(note = #note)] });
+
+    quote! { #f }
+}
+
+fn fns_from_field<'a>(field: &'a Field, rules: &Rules) -> Vec<(Fns, Option<&'a GenericArgument>)> {
+    let mut fns = Vec::new();
+
+    // setters and getters based on field type
+    match &field.ty {
+        Type::Path(type_path) => {
+            if let Some(last_segment) = type_path.path.segments.last() {
+                match last_segment.ident.to_string().as_str() {
+                    "String" => {
+                        fns.push((Fns::Setter(Tys::String), None));
+                        fns.push((Fns::Getter(Tys::String), None));
+                    }
+
+                    "Vec" => {
+                        // Vec<T> -> &[T]
+                        if let PathArguments::AngleBracketed(args) = &last_segment.arguments {
+                            if let Some(arg) = args.args.first() {
+                                if let GenericArgument::Type(ty) = arg {
+                                    if let Type::Path(type_path) = &ty {
+                                        if let Some(last_segment) = type_path.path.segments.last() {
+                                            let ident = &last_segment.ident;
+
+                                            // any iterable of T or Into<T>, or an owned Vec<T>
+                                            fns.push((Fns::Setter(Tys::VecIter), Some(arg)));
+                                            fns.push((Fns::Setter(Tys::VecOwned), Some(arg)));
+
+                                            // single element push
+                                            if rules.each.is_some() {
+                                                fns.push((Fns::Setter(Tys::Each), Some(arg)));
+                                            }
+
+                                            // Vec<String> -> &[&str]
+                                            if ident == "String" {
+                                                fns.push((Fns::Setter(Tys::VecString), None));
+
+                                                // increment ver
+                                                fns.push((Fns::Setter(Tys::VecStringInc), None));
+                                            } else if ident == "u8" {
+                                                // Vec<u8> -> impl AsRef<[u8]>
+                                                fns.push((Fns::Setter(Tys::Bytes), None));
+                                                fns.push((Fns::Setter(Tys::VecInc), Some(arg)));
+                                            } else {
+                                                // setters
+                                                fns.push((Fns::Setter(Tys::Vec), Some(arg)));
+
+                                                // setters inc
+                                                fns.push((Fns::Setter(Tys::VecInc), Some(arg)));
+                                            }
+
+                                            // getters: Vec<T> -> &[T]
+                                            fns.push((Fns::Getter(Tys::Vec), Some(arg)));
+
+                                            // getters: Vec<String> -> impl Iterator<Item = &str>
+                                            if ident == "String" {
+                                                fns.push((Fns::Getter(Tys::VecString), None));
+                                            }
+                                        }
+                                    } else {
+                                        // Vec<T> -> &[T]
+                                        // setters
+                                        fns.push((Fns::Setter(Tys::Vec), Some(arg)));
+
+                                        // setters inc
+                                        fns.push((Fns::Setter(Tys::VecInc), Some(arg)));
+                                        fns.push((Fns::Setter(Tys::VecIter), Some(arg)));
+                                        fns.push((Fns::Setter(Tys::VecOwned), Some(arg)));
+                                        if rules.each.is_some() {
+                                            fns.push((Fns::Setter(Tys::Each), Some(arg)));
+                                        }
+                                        // getters: Vec<T> -> &[T]
+                                        fns.push((Fns::Getter(Tys::Vec), Some(arg)));
+
+                                        // Vec<(K, V)> as an ordered map: insert & lookup
+                                        if matches!(ty, Type::Tuple(tuple) if tuple.elems.len() == 2)
+                                        {
+                                            fns.push((Fns::Setter(Tys::VecPair), Some(arg)));
+                                            fns.push((Fns::Getter(Tys::VecPair), Some(arg)));
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    "Option" => {
+                        // Option<T>
+                        // - T => String => &str
+                        // - T => Vec<U> => &[U]
+                        //   - U => String => &str
+                        if let PathArguments::AngleBracketed(args) = &last_segment.arguments {
+                            if let Some(arg) = &args.args.first() {
+                                if let GenericArgument::Type(ty) = arg {
+                                    if let Type::Path(type_path) = &ty {
+                                        if let Some(last_segment) = type_path.path.segments.last() {
+                                            let ident = &last_segment.ident;
+                                            // T => Vec<U> => &[U]
+                                            if ident == "Vec" {
+                                                if let PathArguments::AngleBracketed(args) =
+                                                    &last_segment.arguments
+                                                {
+                                                    // U
+                                                    if let Some(arg) = args.args.first() {
+                                                        if let GenericArgument::Type(Type::Path(
+                                                            type_path,
+                                                        )) = arg
+                                                        {
+                                                            if let Some(last_segment) =
+                                                                type_path.path.segments.last()
+                                                            {
+                                                                // U => String => &str
+                                                                // Option<Vec<String>> -> Option<&[&str]>
+                                                                if last_segment.ident == "String" {
+                                                                    fns.push((
+                                                                        Fns::Setter(
+                                                                            Tys::OptionVecString,
+                                                                        ),
+                                                                        None,
+                                                                    ));
+                                                                    fns.push((
+                                                                        Fns::Setter(
+                                                                            Tys::OptionVecStringInc,
+                                                                        ),
+                                                                        None,
+                                                                    ));
+                                                                } else {
+                                                                    fns.push((
+                                                                        Fns::Setter(Tys::OptionVec),
+                                                                        Some(arg),
+                                                                    ));
+                                                                    fns.push((
+                                                                        Fns::Setter(
+                                                                            Tys::OptionVecInc,
+                                                                        ),
+                                                                        Some(arg),
+                                                                    ));
+                                                                }
+                                                            }
+                                                        } else {
+                                                            fns.push((
+                                                                Fns::Setter(Tys::OptionVec),
+                                                                Some(arg),
+                                                            ));
+                                                            fns.push((
+                                                                Fns::Setter(Tys::OptionVecInc),
+                                                                Some(arg),
+                                                            ));
+                                                        }
+
+                                                        // getters: Option<Vec<T>> -> Option<&[T]>
+                                                        fns.push((
+                                                            Fns::Getter(Tys::OptionVec),
+                                                            Some(arg),
+                                                        ));
+                                                    }
+                                                }
+                                            } else if ident == "String" {
+                                                // T => String => &str
+                                                fns.push((
+                                                    Fns::Setter(Tys::OptionString),
+                                                    Some(arg),
+                                                ));
+
+                                                // getters: Option<String> -> Option<&str>
+                                                fns.push((
+                                                    Fns::Getter(Tys::OptionString),
+                                                    Some(arg),
+                                                ));
+                                            } else if ident == "PathBuf" {
+                                                // Option<PathBuf> -> Option<&Path>
+                                                fns.push((Fns::Setter(Tys::OptionPathBuf), None));
+                                                fns.push((Fns::Getter(Tys::OptionPathBuf), None));
+                                            } else if ident == "OsString" {
+                                                // Option<OsString> -> Option<&OsStr>
+                                                fns.push((Fns::Setter(Tys::OptionOsString), None));
+                                                fns.push((Fns::Getter(Tys::OptionOsString), None));
+                                            } else if ident == "Box" {
+                                                // Option<Box<T>>: boxed here, read as Option<&T>
+                                                let inner = nth_generic_arg(last_segment, 0);
+                                                let unsized_inner = match inner {
+                                                    Some(GenericArgument::Type(ty)) => {
+                                                        is_unsized(ty)
+                                                    }
+                                                    _ => true,
+                                                };
+                                                if unsized_inner {
+                                                    fns.push((Fns::Setter(Tys::Option), Some(arg)));
+                                                } else {
+                                                    fns.push((Fns::Setter(Tys::OptionBox), inner));
+                                                    fns.push((
+                                                        Fns::Setter(Tys::OptionBoxed),
+                                                        Some(arg),
+                                                    ));
+                                                }
+                                                fns.push((Fns::Getter(Tys::OptionBox), inner));
+                                            } else {
+                                                // T => T
+                                                fns.push((Fns::Setter(Tys::Option), Some(arg)));
+
+                                                // Option<Url>: parsed from &str
+                                                if ident == "Url"
+                                                    && cfg!(feature = "url")
+                                                    && from_crate(type_path, "url")
+                                                {
+                                                    fns.push((Fns::Setter(Tys::OptionUrl), None));
+                                                }
+
+                                                // Option<Regex>: compiled from a pattern
+                                                if ident == "Regex"
+                                                    && cfg!(feature = "regex")
+                                                    && from_crate(type_path, "regex")
+                                                {
+                                                    fns.push((Fns::Setter(Tys::OptionRegex), None));
+                                                }
+
+                                                if PRIMITIVE_TYPES
+                                                    .contains(&ident.to_string().as_str())
+                                                {
+                                                    // getters: Option<T> -> Option<T>
+                                                    fns.push((Fns::Getter(Tys::Option), Some(arg)));
+                                                } else {
+                                                    // getters: Option<T> -> Option<&T>
+                                                    // Option<Box<T>>, Option<Option<T>>
+                                                    fns.push((
+                                                        Fns::Getter(Tys::OptionAsRef),
+                                                        Some(arg),
+                                                    ));
+                                                }
+                                            }
+                                        }
+                                    } else {
+                                        //  others: Option<(u8, i8)>, Option<&'a str>,
+                                        if let PathArguments::AngleBracketed(args) =
+                                            &last_segment.arguments
+                                        {
+                                            if let Some(arg) = args.args.first() {
+                                                // setters
+                                                fns.push((Fns::Setter(Tys::Option), Some(arg)));
+
+                                                // getters
+                                                if let GenericArgument::Type(ty) = arg {
+                                                    match ty {
+                                                        Type::Reference(_) => {
+                                                            // getters: Option<T> -> Option<T>
+                                                            // Option<&'a str>
+                                                            fns.push((
+                                                                Fns::Getter(Tys::Option),
+                                                                Some(arg),
+                                                            ));
+                                                        }
+                                                        _ => {
+                                                            // getters: Option<T> -> Option<&T>
+                                                            // Option<(u8, i8)>
+                                                            fns.push((
+                                                                Fns::Getter(Tys::OptionAsRef),
+                                                                Some(arg),
+                                                            ));
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    "Cow"
+                        if matches!(
+                            nth_generic_arg(last_segment, 1),
+                            Some(GenericArgument::Type(Type::Path(x))) if x.path.is_ident("str")
+                        ) =>
+                    {
+                        // Cow<'a, str>: set from &'a str, String or Cow, read as &str
+                        let lifetime = nth_generic_arg(last_segment, 0);
+                        fns.push((Fns::Setter(Tys::CowStr), lifetime));
+                        fns.push((Fns::Getter(Tys::String), None));
+                    }
+                    "PathBuf" => {
+                        // PathBuf: set from anything path-like, read as &Path
+                        fns.push((Fns::Setter(Tys::PathBuf), None));
+                        fns.push((Fns::Getter(Tys::PathBuf), None));
+                    }
+                    "OsString" => {
+                        // OsString: set from anything OsStr-like, read as &OsStr
+                        fns.push((Fns::Setter(Tys::OsString), None));
+                        fns.push((Fns::Getter(Tys::OsString), None));
+                    }
+                    "CString" => {
+                        // CString: set from &CStr, read as &CStr
+                        fns.push((Fns::Setter(Tys::CString), None));
+                        fns.push((Fns::Getter(Tys::CString), None));
+                    }
+                    "Arc" | "Rc" => {
+                        // the shared pointer itself, or with `_inner` its value wrapped here
+                        let inner = nth_generic_arg(last_segment, 0);
+                        let lock = match inner {
+                            Some(GenericArgument::Type(Type::Path(x))) => x
+                                .path
+                                .segments
+                                .last()
+                                .filter(|x| x.ident == "Mutex" || x.ident == "RwLock"),
+                            _ => None,
+                        };
+                        fns.push((Fns::Setter(Tys::Basic), None));
+                        match lock {
+                            // Arc<Mutex<T>>: `_inner` takes T, wrapped in both
+                            Some(lock) => {
+                                fns.push((Fns::Setter(Tys::SharedLock), nth_generic_arg(lock, 0)))
+                            }
+                            None => {
+                                // Arc<dyn Trait>, Arc<str>, Arc<[T]>: unsized, no `_inner`
+                                if let Some(GenericArgument::Type(ty)) = inner {
+                                    if !is_unsized(ty) {
+                                        fns.push((Fns::Setter(Tys::Shared), inner));
+                                    }
+                                }
+                            }
+                        }
+                        fns.push((Fns::Getter(Tys::Ref), None));
+                    }
+                    "Weak" => {
+                        // rc::Weak<T> & sync::Weak<T>: with_x_from downgrades a strong handle
+                        let arg = nth_generic_arg(last_segment, 0);
+                        let segments = &type_path.path.segments;
+                        fns.push((Fns::Setter(Tys::Basic), None));
+                        if has_type_arg(last_segment) && segments.len() > 1 {
+                            match segments[segments.len() - 2].ident.to_string().as_str() {
+                                "rc" => fns.push((Fns::Setter(Tys::WeakRc), arg)),
+                                "sync" => fns.push((Fns::Setter(Tys::WeakArc), arg)),
+                                _ => {}
+                            }
+                        }
+                        fns.push((Fns::Getter(Tys::Ref), None));
+                    }
+                    "Result" if rules.ok_wrap => {
+                        // Result<T, E>: with_x(T) wraps in Ok, with_x_err(E) wraps in Err
+                        fns.push((Fns::Setter(Tys::ResultOk), nth_generic_arg(last_segment, 0)));
+                        fns.push((
+                            Fns::Setter(Tys::ResultErr),
+                            nth_generic_arg(last_segment, 1),
+                        ));
+                        fns.push((Fns::Getter(Tys::Ref), None));
+                    }
+                    "Result" if nth_generic_arg(last_segment, 1).is_some() => {
+                        // Result<T, E>: with_x(Result), with_x_ok(T) & with_x_err(E)
+                        fns.push((Fns::Setter(Tys::Basic), None));
+                        fns.push((Fns::Setter(Tys::ResultOk), nth_generic_arg(last_segment, 0)));
+                        fns.push((
+                            Fns::Setter(Tys::ResultErr),
+                            nth_generic_arg(last_segment, 1),
+                        ));
+                        fns.push((Fns::Getter(Tys::Ref), None));
+                    }
+                    "LazyLock" | "LazyCell" | "Lazy"
+                        if has_type_arg(last_segment)
+                            && (last_segment.ident != "Lazy"
+                                || from_crate(type_path, "once_cell")) =>
+                    {
+                        // initialized on first access, can not be assigned afterwards
+                        if rules.setter_requested {
+                            panic!(
+                                "Builder(aksr): `{}` can not be set after construction, remove `setter = true`",
+                                last_segment.ident
+                            );
+                        }
+                        fns.push((Fns::Getter(Tys::Lazy), nth_generic_arg(last_segment, 0)));
+                    }
+                    "Cell" if has_type_arg(last_segment) => {
+                        // Cell<T: Copy>: set & get the value, not the cell
+                        let arg = nth_generic_arg(last_segment, 0);
+                        fns.push((Fns::Setter(Tys::Cell), arg));
+                        fns.push((Fns::Getter(Tys::Cell), arg));
+                    }
+                    "RefCell" if has_type_arg(last_segment) => {
+                        // RefCell<T>: set the value, read through the cell
+                        let arg = nth_generic_arg(last_segment, 0);
+                        fns.push((Fns::Setter(Tys::RefCell), arg));
+                        fns.push((Fns::Getter(Tys::Ref), None));
+                    }
+                    "Mutex" | "RwLock"
+                        if cfg!(feature = "tokio") && from_crate(type_path, "tokio") =>
+                    {
+                        // tokio::sync locks: inner value setter, try_lock/try_read beside the getter
+                        let arg = nth_generic_arg(last_segment, 0);
+                        fns.push((Fns::Setter(Tys::Basic), None));
+                        fns.push((Fns::Setter(Tys::Lock), arg));
+                        fns.push((Fns::Getter(Tys::Ref), None));
+                        if last_segment.ident == "Mutex" {
+                            fns.push((Fns::Getter(Tys::Mutex), arg));
+                        } else {
+                            fns.push((Fns::Getter(Tys::RwLock), arg));
+                        }
+                    }
+                    "Array1" | "Array2"
+                        if cfg!(feature = "ndarray") && from_crate(type_path, "ndarray") =>
+                    {
+                        // ndarray: built from slices/vecs, read through views
+                        let arg = nth_generic_arg(last_segment, 0);
+                        if last_segment.ident == "Array1" {
+                            fns.push((Fns::Setter(Tys::Array1), arg));
+                            fns.push((Fns::Getter(Tys::Array1), arg));
+                        } else {
+                            fns.push((Fns::Setter(Tys::Basic), None));
+                            fns.push((Fns::Setter(Tys::Array2), arg));
+                            fns.push((Fns::Getter(Tys::Array2), arg));
+                        }
+                    }
+                    "Vec2" | "Vec3" | "Vec4" | "DVec2" | "DVec3" | "DVec4" | "IVec2" | "IVec3"
+                    | "IVec4" | "UVec2" | "UVec3" | "UVec4"
+                        if cfg!(feature = "glam") && from_crate(type_path, "glam") =>
+                    {
+                        // glam: Copy vectors, also set from components or arrays
+                        fns.push((Fns::Setter(Tys::Basic), None));
+                        fns.push((Fns::Setter(Tys::Vector), None));
+                        fns.push((Fns::Getter(Tys::Basic), None));
+                    }
+                    "Vector2" | "Vector3" | "Vector4"
+                        if cfg!(feature = "nalgebra") && from_crate(type_path, "nalgebra") =>
+                    {
+                        // nalgebra: Vector<T> of Copy scalars, same shape as glam
+                        fns.push((Fns::Setter(Tys::Basic), None));
+                        fns.push((Fns::Setter(Tys::Vector), nth_generic_arg(last_segment, 0)));
+                        fns.push((Fns::Getter(Tys::Basic), None));
+                    }
+                    "DateTime" if cfg!(feature = "chrono") && from_crate(type_path, "chrono") => {
+                        // chrono: exact type, or parsed from RFC3339
+                        fns.push((Fns::Setter(Tys::Basic), None));
+                        fns.push((Fns::Setter(Tys::DateTime), None));
+                        fns.push((Fns::Getter(Tys::Ref), None));
+                    }
+                    "OffsetDateTime" if cfg!(feature = "time") && from_crate(type_path, "time") => {
+                        // time: exact type, or parsed from RFC3339
+                        fns.push((Fns::Setter(Tys::Basic), None));
+                        fns.push((Fns::Setter(Tys::OffsetDateTime), None));
+                        fns.push((Fns::Getter(Tys::Ref), None));
+                    }
+                    "Url" if cfg!(feature = "url") && from_crate(type_path, "url") => {
+                        // url: exact type, or parsed from &str
+                        fns.push((Fns::Setter(Tys::Basic), None));
+                        fns.push((Fns::Setter(Tys::Url), None));
+                        fns.push((Fns::Getter(Tys::Ref), None));
+                    }
+                    "Regex" if cfg!(feature = "regex") && from_crate(type_path, "regex") => {
+                        // regex: exact type, or compiled from a pattern
+                        fns.push((Fns::Setter(Tys::Basic), None));
+                        fns.push((Fns::Setter(Tys::Regex), None));
+                        fns.push((Fns::Getter(Tys::Ref), None));
+                    }
+                    "HashMap" | "BTreeMap" => {
+                        fns.push((Fns::Setter(Tys::Basic), None));
+                        fns.push((Fns::Setter(Tys::Extend), None));
+                        fns.push((Fns::Getter(Tys::Ref), None));
+
+                        // getters: map of String -> String -> impl Iterator<Item = (&str, &str)>
+                        let is_string = |n| {
+                            matches!(
+                                nth_generic_arg(last_segment, n),
+                                Some(GenericArgument::Type(Type::Path(x)))
+                                    if x.path.segments.last().is_some_and(|x| x.ident == "String")
+                            )
+                        };
+                        if is_string(0) && is_string(1) {
+                            fns.push((Fns::Getter(Tys::MapString), None));
+                        }
+                    }
+                    "HashSet" | "BTreeSet" => {
+                        fns.push((Fns::Setter(Tys::Basic), None));
+                        fns.push((Fns::Setter(Tys::Extend), None));
+                        fns.push((Fns::Getter(Tys::Ref), None));
+                    }
+                    "VecDeque" | "BinaryHeap" => {
+                        // push_back & push_front, or push into the heap
+                        fns.push((Fns::Setter(Tys::Basic), None));
+                        fns.push((Fns::Setter(Tys::Extend), None));
+                        fns.push((Fns::Setter(Tys::Push), nth_generic_arg(last_segment, 0)));
+                        fns.push((Fns::Getter(Tys::Ref), None));
+                    }
+                    _ if nonzero_raw(&field.ty).is_some() => {
+                        // NonZeroU32 <- u32, read back as u32
+                        fns.push((Fns::Setter(Tys::Basic), None));
+                        let try_range = rules.range.is_some() && rules.range_mode == RangeMode::Try;
+                        if rules.validate.is_none() && !rules.try_into && !try_range {
+                            fns.push((Fns::Setter(Tys::NonZero), None));
+                        }
+                        fns.push((Fns::Getter(Tys::NonZero), None));
+                    }
+                    xxx => {
+                        fns.push((Fns::Setter(Tys::Basic), None));
+                        if PRIMITIVE_TYPES.contains(&xxx) {
+                            fns.push((Fns::Getter(Tys::Basic), None));
+                        } else {
+                            fns.push((Fns::Getter(Tys::Ref), None));
+                        }
+                    }
+                }
+            }
+        }
+        ty => {
+            // setter
+            fns.push((Fns::Setter(Tys::Basic), None));
+
+            // getter
+            match ty {
+                Type::Reference(_) => {
+                    // &'a T or &'a mut T
+                    fns.push((Fns::Getter(Tys::Basic), None));
+                }
+                Type::Array(_) | Type::Tuple(_) => {
+                    // array [T; n] and tuple (A, B, C, String)
+                    fns.push((Fns::Getter(Tys::Ref), None));
+
+                    // [T; n] <- &[T], unless another `try_` setter is asked for
+                    let try_range = rules.range.is_some() && rules.range_mode == RangeMode::Try;
+                    if matches!(ty, Type::Array(_))
+                        && rules.validate.is_none()
+                        && !rules.try_into
+                        && !try_range
+                    {
+                        fns.push((Fns::Setter(Tys::ArraySlice), None));
+                    }
+                }
+                _ => {
+                    // TODO: others
+                    fns.push((Fns::Getter(Tys::Ref), None));
+                }
+            }
+        }
+    }
+
+    if rules.each.is_some() && !fns.iter().any(|(x, _)| matches!(x, Fns::Setter(Tys::Each))) {
+        panic!("Builder(aksr): `each` is only supported on `Vec` fields");
+    }
+
+    // collections: empty in place
+    if matches!(
+        &field.ty,
+        Type::Path(x) if x.path.segments.last().is_some_and(|x| matches!(
+            x.ident.to_string().as_str(),
+            "Vec" | "VecDeque" | "BinaryHeap" | "HashMap" | "BTreeMap" | "HashSet" | "BTreeSet"
+        ))
+    ) {
+        fns.push((Fns::Setter(Tys::Clear), None));
+    }
+
+    // complex fields: adjust in place through a closure
+    if matches!(
+        &field.ty,
+        Type::Path(x) if x.path.segments.last().is_some_and(|x| {
+            let ident = x.ident.to_string();
+            ident != "String" && !PRIMITIVE_TYPES.contains(&ident.as_str())
+        })
+    ) {
+        fns.push((Fns::Setter(Tys::Modify), None));
+    }
+
+    // Option fields: pass an `Option<T>` through as is
+    if let Some((_, arg)) = fns.iter().find(|(x, _)| {
+        matches!(
+            x,
+            Fns::Setter(
+                Tys::Option
+                    | Tys::OptionBox
+                    | Tys::OptionPathBuf
+                    | Tys::OptionOsString
+                    | Tys::OptionString
+                    | Tys::OptionVec
+                    | Tys::OptionVecString
+            )
+        )
+    }) {
+        let arg = match fns
+            .iter()
+            .any(|(x, _)| matches!(x, Fns::Setter(Tys::Option)))
+        {
+            true => *arg,
+            false => None,
+        };
+        fns.push((Fns::Setter(Tys::OptionOpt), arg));
+        fns.push((Fns::Setter(Tys::Without), None));
+    }
+
+    // `into`: the assigning setter takes `impl Into<T>` instead
+    if rules.into {
+        for (fn_type, arg) in fns.iter_mut() {
+            if matches!(
+                fn_type,
+                Fns::Setter(
+                    Tys::Basic
+                        | Tys::String
+                        | Tys::Vec
+                        | Tys::VecString
+                        | Tys::Bytes
+                        | Tys::Option
+                        | Tys::OptionBox
+                        | Tys::PathBuf
+                        | Tys::OptionPathBuf
+                        | Tys::OsString
+                        | Tys::OptionOsString
+                        | Tys::CString
+                        | Tys::OptionString
+                        | Tys::OptionVec
+                        | Tys::OptionVecString
+                )
+            ) {
+                *fn_type = Fns::Setter(Tys::Into);
+                *arg = None;
+            }
+        }
+    }
+
+    fns
+}
+
+fn generate_is_default(
+    data_struct: &DataStruct,
+    defaults: &[Option<syn::Expr>],
+) -> proc_macro2::TokenStream {
+    // compare every field against its declared default, no `PartialEq` needed on the struct itself;
+    // the `track_set` bitset records how fields were set, not their values, so it is left out
+    let comparisons = data_struct
+        .fields
+        .iter()
+        .zip(defaults)
+        .enumerate()
+        .filter(|(_, (field, _))| !Rules::from(*field).track_set)
+        .map(|(idx, (field, default))| {
+            let field_type = &field.ty;
+            let field_index = Index::from(idx);
+            let field_access = field
+                .ident
+                .as_ref()
+                .map_or_else(|| quote! { #field_index }, |name| quote! { #name });
+            let default = default_value(field, default.clone());
+            quote! {
+                && self.#field_access == { let default: #field_type = #default; default }
+            }
+        });
+
+    quote! {
+        pub fn is_default(&self) -> bool {
+            true #(#comparisons)*
+        }
+    }
+}
+
+fn generate_ffi(
+    st: &DeriveInput,
+    data_struct: &DataStruct,
+    struct_rules: &StructRules,
+) -> proc_macro2::TokenStream {
+    // `#[no_mangle] extern "C"` getters for primitive & pointer fields
+    if !st.generics.params.is_empty() {
+        panic!("Builder(aksr): `ffi` can not be used on generic structs");
+    }
+    // C callers rely on the field layout
+    let repr_c = st.attrs.iter().any(|attr| {
+        attr.path().is_ident("repr")
+            && attr
+                .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                .is_ok_and(|metas| metas.iter().any(|meta| meta.path().is_ident("C")))
+    });
+    if !repr_c {
+        return quote! {
+            ::core::compile_error!("Builder(aksr): `ffi` requires `#[repr(C)]` on the struct");
+        };
+    }
+    let struct_name = &st.ident;
+    let prefix = to_snake_case(&struct_name.to_string());
+    let mut shims = quote! {};
+    for (idx, field) in data_struct.fields.iter().enumerate() {
+        let field_type = &field.ty;
+        // `char` has no C counterpart, `unit` is not a type
+        let representable = match field_type {
+            Type::Ptr(_) => true,
+            Type::Path(type_path) => type_path.path.get_ident().is_some_and(|x| {
+                let x = x.to_string();
+                x != "char" && x != "unit" && PRIMITIVE_TYPES.contains(&x.as_str())
+            }),
+            _ => false,
+        };
+        if !representable {
+            continue;
+        }
+        let field_index = Index::from(idx);
+        let field_access = field
+            .ident
+            .as_ref()
+            .map_or_else(|| quote! { #field_index }, |name| quote! { #name });
+        let fn_name = Ident::new(
+            &format!(
+                "{}_{}_get",
+                prefix,
+                Rules::from(field).base_name(field, idx)
+            ),
+            Span::call_site(),
+        );
+        let doc = format!(" Read `{}` of a `{}` from C.", field_access, struct_name);
+        let doc = (!struct_rules.skip_docs).then(|| {
+            quote! {
+                #[doc = #doc]
+                ///
+                /// # Safety
+                ///
+                /// `ptr` must be a valid, aligned pointer to an initialized value.
+            }
+        });
+        shims.extend(quote! {
+            #doc
+            #[unsafe(no_mangle)]
+            pub unsafe extern "C" fn #fn_name(ptr: *const #struct_name) -> #field_type {
+                unsafe { (*ptr).#field_access }
+            }
+        });
+    }
+
+    shims
+}
+
+fn generate_patch(
+    st: &DeriveInput,
+    data_struct: &DataStruct,
+    struct_rules: &StructRules,
+) -> (Ident, proc_macro2::TokenStream) {
+    // FooPatch: every field as Option<T>, applied onto Foo field by field
+    let struct_name = &st.ident;
+    let patch_name = Ident::new(&format!("{}Patch", struct_name), Span::call_site());
+    let generics = &st.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let vis = &st.vis;
+
+    let mut fields = Vec::new();
+    let mut nones = Vec::new();
+    let mut applies = Vec::new();
+    let mut setters = quote! {};
+    for (idx, field) in data_struct.fields.iter().enumerate() {
+        if Rules::from(field).track_set {
+            continue;
+        }
+        let field_type = &field.ty;
+
+        // setters of the patch field, as for any `Option<T>` field
+        let mut patch_field = field.clone();
+        patch_field.attrs.clear();
+        patch_field.ty = syn::parse_quote! { Option<#field_type> };
+        let rules = Rules::default();
+        for (fn_type, arg) in fns_from_field(&patch_field, &rules) {
+            if let Fns::Setter(_) = fn_type {
+                generate(
+                    &patch_field,
+                    &rules,
+                    idx,
+                    &StructRules::default(),
+                    arg,
+                    &mut setters,
+                    fn_type,
+                );
+            }
+        }
+
+        let field_index = Index::from(idx);
+        let field_access = field
+            .ident
+            .as_ref()
+            .map_or_else(|| quote! { #field_index }, |name| quote! { #name });
+        let post_set = match &struct_rules.tracker {
+            Some(tracker) => quote! { target.#tracker |= 1 << #idx; },
+            None => quote! {},
+        };
+        match &field.ident {
+            Some(name) => {
+                fields.push(quote! { pub #name: Option<#field_type> });
+                nones.push(quote! { #name: None });
+            }
+            None => {
+                fields.push(quote! { pub Option<#field_type> });
+                nones.push(quote! { None });
+            }
+        }
+        applies.push(quote! {
+            if let Some(x) = self.#field_access {
+                target.#field_access = x;
+                #post_set
+            }
+        });
+    }
+
+    let (patch_struct, patch_none) = match &data_struct.fields {
+        syn::Fields::Named(_) => (
+            quote! { #vis struct #patch_name #generics #where_clause { #(#fields,)* } },
+            quote! { #patch_name { #(#nones,)* } },
+        ),
+        _ => (
+            quote! { #vis struct #patch_name #generics ( #(#fields,)* ) #where_clause; },
+            quote! { #patch_name ( #(#nones,)* ) },
+        ),
+    };
+
+    let derive_serde = match struct_rules.gen_merge_json {
+        true => quote! { #[derive(::serde::Deserialize)] },
+        false => quote! {},
+    };
+    let items = quote! {
+        #derive_serde
+        #patch_struct
+
+        impl #impl_generics Default for #patch_name #ty_generics #where_clause {
+            fn default() -> Self {
+                #patch_none
+            }
+        }
+
+        impl #impl_generics #patch_name #ty_generics #where_clause {
+            #setters
+
+            pub fn apply(self, target: &mut #struct_name #ty_generics) {
+                #(#applies)*
+            }
+        }
+    };
+
+    (patch_name, items)
+}
+
+fn generate_view_trait(
+    st: &DeriveInput,
+    accessors: &proc_macro2::TokenStream,
+    trait_path: &syn::Path,
+    define: bool,
+) -> proc_macro2::TokenStream {
+    // every `&self` getter as a trait method, forwarding to the inherent one;
+    // defined by one struct, implemented by others with the same fields
+    let struct_name = &st.ident;
+    let (impl_generics, ty_generics, where_clause) = st.generics.split_for_impl();
+    let vis = &st.vis;
+    let item: ItemImpl = syn::parse_quote! { impl __ { #accessors } };
+    let mut sigs = Vec::new();
+    let mut fns = Vec::new();
+    for impl_item in item.items {
+        let ImplItem::Fn(f) = impl_item else {
+            continue;
+        };
+        let is_getter = matches!(
+            f.sig.inputs.first(),
+            Some(syn::FnArg::Receiver(x)) if x.reference.is_some() && x.mutability.is_none()
+        );
+        if !is_getter {
+            continue;
+        }
+        let sig = &f.sig;
+        let name = &sig.ident;
+        let args = sig.inputs.iter().filter_map(|x| match x {
+            syn::FnArg::Typed(x) => Some(&x.pat),
+            syn::FnArg::Receiver(_) => None,
+        });
+        let attrs = f.attrs.iter().filter(|x| x.path().is_ident("doc"));
+        sigs.push(quote! { #(#attrs)* #sig; });
+        fns.push(quote! {
+            #sig {
+                #struct_name::#name(self, #(#args),*)
+            }
+        });
+    }
+
+    let definition = match define {
+        true => quote! {
+            #vis trait #trait_path #impl_generics #where_clause {
+                #(#sigs)*
+            }
+        },
+        false => quote! {},
+    };
+
+    quote! {
+        #definition
+
+        impl #impl_generics #trait_path #ty_generics for #struct_name #ty_generics #where_clause {
+            #(#fns)*
+        }
+    }
+}
+
+fn generate_view(
+    st: &DeriveInput,
+    data_struct: &DataStruct,
+    suffix: &str,
+    fn_name: &str,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    // FooView<'__view, ...> (or FooRef): getter views of every field
+    let view_name = Ident::new(&format!("{}{}", st.ident, suffix), Span::call_site());
+    let fn_name = Ident::new(fn_name, Span::call_site());
+    let lifetime = quote! { '__view };
+    let mut view_generics = st.generics.clone();
+    view_generics
+        .params
+        .insert(0, syn::parse_quote! { '__view });
+    let (impl_generics, view_ty_generics, where_clause) = view_generics.split_for_impl();
+    let ty_generics_args = st.generics.params.iter().map(|param| match param {
+        GenericParam::Lifetime(x) => {
+            let lifetime = &x.lifetime;
+            quote! { #lifetime }
+        }
+        GenericParam::Type(x) => {
+            let ident = &x.ident;
+            quote! { #ident }
+        }
+        GenericParam::Const(x) => {
+            let ident = &x.ident;
+            quote! { #ident }
+        }
+    });
+    let vis = &st.vis;
+
+    let mut fields = Vec::new();
+    let mut values = Vec::new();
+    for (idx, field) in data_struct.fields.iter().enumerate() {
+        let rules = Rules::from(field);
+        if rules.track_set || rules.writeonly {
+            continue;
+        }
+        let field_index = Index::from(idx);
+        let field_access = field
+            .ident
+            .as_ref()
+            .map_or_else(|| quote! { #field_index }, |name| quote! { #name });
+        let Some((ret, body)) =
+            fns_from_field(field, &rules)
+                .into_iter()
+                .find_map(|(fn_type, arg)| match fn_type {
+                    Fns::Getter(ty) => getter_parts(&ty, &field.ty, arg, &field_access, &lifetime),
+                    Fns::Setter(_) => None,
+                })
+        else {
+            continue;
+        };
+        match &field.ident {
+            Some(name) => {
+                fields.push(quote! { pub #name: #ret });
+                values.push(quote! { #name: #body });
+            }
+            None => {
+                fields.push(quote! { pub #ret });
+                values.push(body);
+            }
+        }
+    }
+
+    // views of `Copy` fields only borrow nothing, keep the lifetime alive with a marker
+    if !fields.iter().any(|x| x.to_string().contains("'__view")) {
+        match &data_struct.fields {
+            syn::Fields::Named(_) => {
+                fields.push(quote! { __aksr: ::core::marker::PhantomData<&#lifetime ()> });
+                values.push(quote! { __aksr: ::core::marker::PhantomData });
+            }
+            _ => {
+                fields.push(quote! { ::core::marker::PhantomData<&#lifetime ()> });
+                values.push(quote! { ::core::marker::PhantomData });
+            }
+        }
+    }
+
+    let (view_struct, view_value) = match &data_struct.fields {
+        syn::Fields::Named(_) => (
+            quote! { #vis struct #view_name #view_generics #where_clause { #(#fields,)* } },
+            quote! { #view_name { #(#values,)* } },
+        ),
+        _ => (
+            quote! { #vis struct #view_name #view_generics ( #(#fields,)* ) #where_clause; },
+            quote! { #view_name ( #(#values,)* ) },
+        ),
+    };
+    let view_fn = quote! {
+        pub fn #fn_name(&self) -> #view_name<'_, #(#ty_generics_args,)*> {
+            #view_value
+        }
+    };
+
+    let items = quote! {
+        #view_struct
+
+        impl #impl_generics Clone for #view_name #view_ty_generics #where_clause {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl #impl_generics Copy for #view_name #view_ty_generics #where_clause {}
+    };
+
+    (view_fn, items)
+}
+
+fn generate_field_id(
+    st: &DeriveInput,
+    data_struct: &DataStruct,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    // FooField: one variant per field; FooFieldValue<'__field, ...>: borrowed values
+    let id_name = Ident::new(&format!("{}Field", st.ident), Span::call_site());
+    let value_name = Ident::new(&format!("{}FieldValue", st.ident), Span::call_site());
+    let mut value_generics = st.generics.clone();
+    value_generics
+        .params
+        .insert(0, syn::parse_quote! { '__field });
+    let (_, _, where_clause) = value_generics.split_for_impl();
+    let ty_generics_args = st.generics.params.iter().map(|param| match param {
+        GenericParam::Lifetime(x) => {
+            let lifetime = &x.lifetime;
+            quote! { #lifetime }
+        }
+        GenericParam::Type(x) => {
+            let ident = &x.ident;
+            quote! { #ident }
+        }
+        GenericParam::Const(x) => {
+            let ident = &x.ident;
+            quote! { #ident }
+        }
+    });
+    let vis = &st.vis;
+
+    let mut variants = Vec::new();
+    let mut names = Vec::new();
+    let mut values = Vec::new();
+    let mut arms = Vec::new();
+    for (idx, field) in data_struct.fields.iter().enumerate() {
+        let rules = Rules::from(field);
+        if rules.track_set || rules.writeonly {
+            continue;
+        }
+        let name = rules.base_name(field, idx);
+        let variant = match name.parse::<usize>() {
+            Ok(_) => Ident::new(&format!("Nth{}", name), Span::call_site()),
+            Err(_) => Ident::new(&to_camel_case(&name), Span::call_site()),
+        };
+        let field_index = Index::from(idx);
+        let field_access = field
+            .ident
+            .as_ref()
+            .map_or_else(|| quote! { #field_index }, |name| quote! { #name });
+        let field_type = &field.ty;
+        values.push(quote! { #variant(&'__field #field_type) });
+        arms.push(quote! { #id_name::#variant => #value_name::#variant(&self.#field_access) });
+        names.push(quote! { #id_name::#variant => #name });
+        variants.push(variant);
+    }
+    let n = variants.len();
+
+    let get_fn = quote! {
+        pub fn get(&self, field: #id_name) -> #value_name<'_, #(#ty_generics_args,)*> {
+            match field {
+                #(#arms,)*
+            }
+        }
+    };
+
+    let items = quote! {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #vis enum #id_name {
+            #(#variants,)*
+        }
+
+        impl #id_name {
+            pub const ALL: [#id_name; #n] = [#(#id_name::#variants,)*];
+
+            pub fn name(&self) -> &'static str {
+                match self {
+                    #(#names,)*
+                }
+            }
+        }
+
+        #vis enum #value_name #value_generics #where_clause {
+            #(#values,)*
+        }
+    };
+
+    (get_fn, items)
+}
+
+fn generate_track_set(
+    data_struct: &DataStruct,
+    struct_rules: &mut StructRules,
+) -> proc_macro2::TokenStream {
+    // the field holding the bitset, user declared since derive can not add fields
+    let Some((tracker_idx, tracker)) = data_struct
+        .fields
+        .iter()
+        .enumerate()
+        .find(|(_, field)| Rules::from(*field).track_set)
+    else {
+        return quote! {};
+    };
+    let bits = match &tracker.ty {
+        Type::Path(type_path) => match type_path.path.get_ident().map(|i| i.to_string()).as_deref()
+        {
+            Some("u8") => 8,
+            Some("u16") => 16,
+            Some("u32") => 32,
+            Some("u64") => 64,
+            Some("u128") => 128,
+            _ => 0,
+        },
+        _ => 0,
+    };
+    if bits == 0 {
+        panic!("Builder(aksr): `track_set` field must be one of u8, u16, u32, u64, u128");
+    }
+    if data_struct.fields.len() > bits {
+        panic!(
+            "Builder(aksr): `track_set` field of {} bits can not track {} fields",
+            bits,
+            data_struct.fields.len()
+        );
+    }
+    let tracker_index = Index::from(tracker_idx);
+    let tracker = tracker
+        .ident
+        .as_ref()
+        .map_or_else(|| quote! { #tracker_index }, |name| quote! { #name });
+
+    // field name -> bit
+    let (names, bits): (Vec<_>, Vec<_>) = data_struct
+        .fields
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| *idx != tracker_idx)
+        .map(|(idx, field)| {
+            let name = field
+                .ident
+                .as_ref()
+                .map_or_else(|| idx.to_string(), |name| name.to_string());
+            (name, idx)
+        })
+        .unzip();
+    let code = quote! {
+        pub fn is_set(&self, field: &str) -> bool {
+            match field {
+                #(#names => self.#tracker & (1 << #bits) != 0,)*
+                _ => false,
+            }
+        }
+
+        pub fn set_fields(&self) -> Vec<&'static str> {
+            let mut fields = Vec::new();
+            #(
+                if self.#tracker & (1 << #bits) != 0 {
+                    fields.push(#names);
+                }
+            )*
+            fields
+        }
+    };
+    struct_rules.tracker = Some(tracker);
+
+    code
+}
+
+fn generate(
+    field: &Field,
+    rules: &Rules,
+    idx: usize,
+    struct_rules: &StructRules,
+    arg: Option<&GenericArgument>,
+    codes: &mut proc_macro2::TokenStream,
+    fn_type: Fns,
+) {
+    // `setters = "both"`: consuming `with_x` and in-place `set_x` versions of prefixed setters
+    if rules.setters == (true, true) && matches!(fn_type, Fns::Setter(_)) {
+        let mut owned = rules.clone();
+        owned.setters = (true, false);
+        generate(field, &owned, idx, struct_rules, arg, codes, fn_type);
+        if !matches!(
+            fn_type,
+            Fns::Setter(
+                Tys::TakeFrom
+                    | Tys::Zeroize
+                    | Tys::Clear
+                    | Tys::Modify
+                    | Tys::Without
+                    | Tys::Reset
+                    | Tys::MapFn
+                    | Tys::Toggle
+            )
+        ) {
+            let mut by_ref = rules.clone();
+            by_ref.setters = (false, true);
+            by_ref.prefix_setter = SETTER_PREFIX_BY_REF.into();
+            generate(field, &by_ref, idx, struct_rules, arg, codes, fn_type);
+        }
+        return;
+    }
+
+    // setter_name & getter_name
+    let (setter_name, getter_name) = rules.generate_setter_getter_names(field, idx); // (move inside????)
+
+    // attrs
+    let field_type = &field.ty;
+    let field_name = field.ident.as_ref();
+    let field_index = Index::from(idx);
+    let field_access = field_name.map_or_else(|| quote! { #field_index }, |name| quote! { #name });
+
+    let is_setter = matches!(fn_type, Fns::Setter(_));
+    let is_map_fn = matches!(fn_type, Fns::Setter(Tys::MapFn));
+    // setters replacing the whole field from a single value
+    let is_assigning = matches!(
+        fn_type,
+        Fns::Setter(
+            Tys::Basic
+                | Tys::Into
+                | Tys::String
+                | Tys::CowStr
+                | Tys::PathBuf
+                | Tys::OptionPathBuf
+                | Tys::OsString
+                | Tys::OptionOsString
+                | Tys::CString
+                | Tys::Vec
+                | Tys::VecString
+                | Tys::Bytes
+                | Tys::Option
+                | Tys::OptionBox
+                | Tys::OptionString
+                | Tys::OptionVec
+                | Tys::OptionVecString
+        )
+    );
+
+    // statements run on the incoming value `x`, before assignment
+    let clamp = match &rules.range {
+        Some((range, _)) if rules.range_mode == RangeMode::Clamp => Some(range),
+        _ => rules.clamp.as_ref(),
+    };
+    let mut pre_set = match &rules.map {
+        Some(map) => quote! { let x = #map(x); },
+        None => quote! {},
+    };
+    pre_set.extend(match clamp {
+        Some(range) => match (&range.start, &range.end) {
+            (Some(lo), Some(hi)) => quote! { let x = x.clamp(#lo, #hi); },
+            (Some(lo), None) => quote! { let x = x.max(#lo); },
+            (None, Some(hi)) => quote! { let x = x.min(#hi); },
+            (None, None) => quote! {},
+        },
+        None => quote! {},
+    });
+    // `try_` setters report out-of-range values instead
+    if let (Some((range, text)), RangeMode::Panic | RangeMode::Try, false) = (
+        &rules.range,
+        rules.range_mode,
+        matches!(fn_type, Fns::Setter(Tys::Range)),
+    ) {
+        let name = rules.base_name(field, idx);
+        pre_set.extend(quote! {
+            if !(#range).contains(&x) {
+                panic!("`{}` out of range {}", #name, #text);
+            }
+        });
+    }
+
+    // statements run after every assignment
+    let mut post_set = match &struct_rules.tracker {
+        Some(tracker) => quote! { self.#tracker |= 1 << #idx; },
+        None => quote! {},
+    };
+    // `try_` setters validate up front and return the error instead
+    if let (Some(validate), false) = (
+        &rules.validate,
+        matches!(fn_type, Fns::Setter(Tys::Validate)),
+    ) {
+        let name = rules.base_name(field, idx);
+        post_set.extend(quote! {
+            if let Err(e) = #validate(&self.#field_access) {
+                panic!("invalid value for `{}`: {}", #name, e);
+            }
+        });
+    }
+    if let Some(hook) = &rules.on_set {
+        // `take_*_from`, `clear_*` and `modify_*` already borrow `self` mutably
+        post_set.extend(match fn_type {
+            Fns::Setter(Tys::TakeFrom | Tys::Clear | Tys::Modify) => quote! { #hook(self); },
+            Fns::Setter(Tys::MapFn) => quote! { #hook(&mut self); },
+            _ if rules.by_ref() => quote! { #hook(self); },
+            _ => quote! { #hook(&mut self); },
+        });
+    }
+    match &struct_rules.invariant {
+        Some((check, false)) => post_set.extend(quote! { #check(&self); }),
+        Some((check, true)) => post_set.extend(quote! {
+            #[cfg(debug_assertions)]
+            #check(&self);
+        }),
+        None => {}
+    }
+
+    // token stream
+    let code = match fn_type {
+        Fns::Setter(ty) => {
+            if !rules.gen_setter {
+                return;
+            }
+            match ty {
+                Tys::Basic => {
+                    quote! {
+                        pub fn #setter_name(mut self, x: #field_type) -> Self {
+                            #pre_set
+                            self.#field_access = x;
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::Into => {
+                    quote! {
+                        pub fn #setter_name(mut self, x: impl Into<#field_type>) -> Self {
+                            let x = x.into();
+                            #pre_set
+                            self.#field_access = x;
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::String => {
+                    quote! {
+                        pub fn #setter_name(mut self, x: impl Into<String>) -> Self {
+                            let x = x.into();
+                            #pre_set
+                            self.#field_access = x;
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::Vec => {
+                    let arg = arg.expect("Vec setter requires a generic argument");
+                    // the higher-ranked bound is only checked on use, so non-`Clone` elements
+                    // still get the other setters, e.g. `with_x_vec`
+                    quote! {
+                        pub fn #setter_name(mut self, x: &[#arg]) -> Self
+                        where
+                            for<'__aksr> #arg: Clone,
+                        {
+                            let x = x.to_vec();
+                            #pre_set
+                            self.#field_access = x;
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::Bytes => {
+                    quote! {
+                        pub fn #setter_name(mut self, x: impl AsRef<[u8]>) -> Self {
+                            let x = x.as_ref().to_vec();
+                            #pre_set
+                            self.#field_access = x;
+                            #post_set
+                            self
                         }
-                        xxx => {
-                            generate(
-                                field,
-                                &rules,
-                                idx,
-                                None,
-                                &mut codes,
-                                Fns::Setter(Tys::Basic),
-                            );
-                            if PRIMITIVE_TYPES.contains(&xxx) {
-                                generate(
-                                    field,
-                                    &rules,
-                                    idx,
-                                    None,
-                                    &mut codes,
-                                    Fns::Getter(Tys::Basic),
-                                );
+                    }
+                }
+                Tys::VecInc if rules.inc_for_vec => {
+                    let arg = arg.expect("VecInc setter requires a generic argument");
+                    let setter_name = Ident::new(
+                        &format!("{}_{}", setter_name, INC_FOR_VEC),
+                        Span::call_site(),
+                    );
+                    let setter_name_iter =
+                        Ident::new(&format!("{}_iter", setter_name), Span::call_site());
+                    quote! {
+                        pub fn #setter_name(mut self, x: &[#arg]) -> Self
+                        where
+                            for<'__aksr> #arg: Clone,
+                        {
+                            if self.#field_access.is_empty() {
+                                self.#field_access = Vec::from(x);
+                            } else {
+                                self.#field_access.extend_from_slice(x);
+                            }
+                            #post_set
+                            self
+                        }
+
+                        pub fn #setter_name_iter(mut self, x: impl IntoIterator<Item = #arg>) -> Self {
+                            self.#field_access.extend(x);
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::VecOwned => {
+                    let arg = arg.expect("VecOwned setter requires a generic argument");
+                    let setter_name =
+                        Ident::new(&format!("{}_vec", setter_name), Span::call_site());
+                    quote! {
+                        pub fn #setter_name(mut self, x: Vec<#arg>) -> Self {
+                            #pre_set
+                            self.#field_access = x;
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::VecIter => {
+                    let arg = arg.expect("VecIter setter requires a generic argument");
+                    let setter_name =
+                        Ident::new(&format!("{}_iter", setter_name), Span::call_site());
+                    quote! {
+                        pub fn #setter_name(mut self, x: impl IntoIterator<Item = impl Into<#arg>>) -> Self {
+                            let x: Vec<#arg> = x.into_iter().map(Into::into).collect();
+                            #pre_set
+                            self.#field_access = x;
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::Each => {
+                    let arg = arg.expect("Each setter requires a generic argument");
+                    let each = rules.each.as_deref().unwrap_or_default();
+                    let fn_name = Ident::new(
+                        &format!("{}_{}", rules.prefix_setter, each),
+                        Span::call_site(),
+                    );
+                    if fn_name == setter_name {
+                        panic!(
+                            "Builder(aksr): `each = {:?}` collides with the setter of the whole field",
+                            each
+                        );
+                    }
+                    // `into` widens the element too
+                    let (ty, value) = match rules.into {
+                        true => (quote! { impl Into<#arg> }, quote! { x.into() }),
+                        false => (quote! { #arg }, quote! { x }),
+                    };
+                    quote! {
+                        pub fn #fn_name(mut self, x: #ty) -> Self {
+                            self.#field_access.push(#value);
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::Extend if rules.inc_for_vec => {
+                    let setter_name = Ident::new(
+                        &format!("{}_{}", setter_name, INC_FOR_VEC),
+                        Span::call_site(),
+                    );
+                    quote! {
+                        pub fn #setter_name(
+                            mut self,
+                            x: impl IntoIterator<Item = <#field_type as IntoIterator>::Item>,
+                        ) -> Self {
+                            self.#field_access.extend(x);
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::Push if rules.inc_for_vec => {
+                    let arg = arg.expect("Push setter requires a generic argument");
+                    let is_deque = matches!(
+                        field_type,
+                        Type::Path(x) if x.path.segments.last().is_some_and(|x| x.ident == "VecDeque")
+                    );
+                    let methods: &[&str] = match is_deque {
+                        true => &["push_back", "push_front"],
+                        false => &["push"],
+                    };
+                    let mut codes = quote! {};
+                    for method in methods {
+                        let fn_name =
+                            Ident::new(&format!("{}_{}", setter_name, method), Span::call_site());
+                        let method = Ident::new(method, Span::call_site());
+                        codes.extend(quote! {
+                            pub fn #fn_name(mut self, x: #arg) -> Self {
+                                self.#field_access.#method(x);
+                                #post_set
+                                self
+                            }
+                        });
+                    }
+                    codes
+                }
+                Tys::VecString => {
+                    quote! {
+                        pub fn #setter_name(mut self, x: &[&str]) -> Self {
+                            let x: Vec<String> = x.iter().map(|s| s.to_string()).collect();
+                            #pre_set
+                            self.#field_access = x;
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::VecStringInc if rules.inc_for_vec => {
+                    let setter_name = Ident::new(
+                        &format!("{}_{}", setter_name, INC_FOR_VEC),
+                        Span::call_site(),
+                    );
+                    let setter_name_iter =
+                        Ident::new(&format!("{}_iter", setter_name), Span::call_site());
+                    quote! {
+                        pub fn #setter_name(mut self, x: &[&str]) -> Self {
+                            if self.#field_access.is_empty() {
+                                self.#field_access = x.iter().map(|s| s.to_string()).collect();
                             } else {
-                                generate(
-                                    field,
-                                    &rules,
-                                    idx,
-                                    None,
-                                    &mut codes,
-                                    Fns::Getter(Tys::Ref),
-                                );
+                                let mut x = x.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+                                self.#field_access.append(&mut x);
                             }
+                            #post_set
+                            self
+                        }
+
+                        pub fn #setter_name_iter(
+                            mut self,
+                            x: impl IntoIterator<Item = impl Into<String>>,
+                        ) -> Self {
+                            self.#field_access.extend(x.into_iter().map(Into::into));
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::Option => {
+                    quote! {
+                        pub fn #setter_name(mut self, x: #arg) -> Self {
+                            #pre_set
+                            self.#field_access = Some(x);
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::MapFn => {
+                    let fn_name = Ident::new(
+                        &format!("map_{}", rules.base_name(field, idx)),
+                        Span::call_site(),
+                    );
+                    quote! {
+                        pub fn #fn_name(mut self, f: impl FnOnce(#field_type) -> #field_type) -> Self {
+                            let x = f(self.#field_access);
+                            #pre_set
+                            self.#field_access = x;
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::Modify => {
+                    let fn_name = Ident::new(
+                        &format!("modify_{}", rules.base_name(field, idx)),
+                        Span::call_site(),
+                    );
+                    quote! {
+                        pub fn #fn_name(&mut self, f: impl FnOnce(&mut #field_type)) -> &mut Self {
+                            f(&mut self.#field_access);
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::Clear => {
+                    let fn_name = Ident::new(
+                        &format!("clear_{}", rules.base_name(field, idx)),
+                        Span::call_site(),
+                    );
+                    quote! {
+                        pub fn #fn_name(&mut self) -> &mut Self {
+                            self.#field_access.clear();
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::Toggle => {
+                    let name = rules.base_name(field, idx);
+                    let enable = Ident::new(&format!("enable_{}", name), Span::call_site());
+                    let disable = Ident::new(&format!("disable_{}", name), Span::call_site());
+                    quote! {
+                        pub fn #enable(mut self) -> Self {
+                            self.#field_access = true;
+                            #post_set
+                            self
+                        }
+
+                        pub fn #disable(mut self) -> Self {
+                            self.#field_access = false;
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::Reset => {
+                    let fn_name = Ident::new(
+                        &format!("reset_{}", rules.base_name(field, idx)),
+                        Span::call_site(),
+                    );
+                    let value = default_value(field, rules.default.clone());
+                    quote! {
+                        pub fn #fn_name(mut self) -> Self {
+                            self.#field_access = #value;
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::Without => {
+                    let fn_name = Ident::new(
+                        &format!("without_{}", rules.base_name(field, idx)),
+                        Span::call_site(),
+                    );
+                    quote! {
+                        pub fn #fn_name(mut self) -> Self {
+                            self.#field_access = None;
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::OptionBox => {
+                    quote! {
+                        pub fn #setter_name(mut self, x: #arg) -> Self {
+                            #pre_set
+                            self.#field_access = Some(Box::new(x));
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::OptionBoxed => {
+                    let setter_name =
+                        Ident::new(&format!("{}_boxed", setter_name), Span::call_site());
+                    quote! {
+                        pub fn #setter_name(mut self, x: #arg) -> Self {
+                            self.#field_access = Some(x);
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::OptionOpt => {
+                    let setter_name =
+                        Ident::new(&format!("{}_opt", setter_name), Span::call_site());
+                    // plain `Option<T>` setters run their checks on the inner value
+                    let value = match (arg.is_some(), pre_set.is_empty()) {
+                        (true, false) => quote! { x.map(|x| { #pre_set x }) },
+                        _ => quote! { x },
+                    };
+                    quote! {
+                        pub fn #setter_name(mut self, x: #field_type) -> Self {
+                            self.#field_access = #value;
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::OptionVec => {
+                    let arg = arg.expect("OptionVec setter requires a generic argument");
+                    quote! {
+                        pub fn #setter_name(mut self, x: &[#arg]) -> Self
+                        where
+                            for<'__aksr> #arg: Clone,
+                        {
+                            let x = x.to_vec();
+                            #pre_set
+                            self.#field_access = Some(x);
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::OptionVecInc if rules.inc_for_vec => {
+                    let arg = arg.expect("OptionVecInc setter requires a generic argument");
+                    let setter_name = Ident::new(
+                        &format!("{}_{}", setter_name, INC_FOR_VEC),
+                        Span::call_site(),
+                    );
+                    let setter_name_iter =
+                        Ident::new(&format!("{}_iter", setter_name), Span::call_site());
+                    quote! {
+                        pub fn #setter_name(mut self, x: &[#arg]) -> Self
+                        where
+                            for<'__aksr> #arg: Clone,
+                        {
+                            self.#field_access
+                                .get_or_insert_with(Vec::new)
+                                .extend_from_slice(x);
+                            #post_set
+                            self
+                        }
+
+                        pub fn #setter_name_iter(mut self, x: impl IntoIterator<Item = #arg>) -> Self {
+                            self.#field_access.get_or_insert_with(Vec::new).extend(x);
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::OptionVecStringInc if rules.inc_for_vec => {
+                    let setter_name = Ident::new(
+                        &format!("{}_{}", setter_name, INC_FOR_VEC),
+                        Span::call_site(),
+                    );
+                    let setter_name_iter =
+                        Ident::new(&format!("{}_iter", setter_name), Span::call_site());
+                    quote! {
+                        pub fn #setter_name(mut self, x: &[&str]) -> Self {
+                            self.#field_access
+                                .get_or_insert_with(Vec::new)
+                                .extend(x.iter().map(|s| s.to_string()));
+                            #post_set
+                            self
+                        }
+
+                        pub fn #setter_name_iter(
+                            mut self,
+                            x: impl IntoIterator<Item = impl Into<String>>,
+                        ) -> Self {
+                            self.#field_access
+                                .get_or_insert_with(Vec::new)
+                                .extend(x.into_iter().map(Into::into));
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::OptionVecString => {
+                    quote! {
+                        pub fn #setter_name(mut self, x: &[&str]) -> Self {
+                            let x: Vec<String> = x.iter().map(|s| s.to_string()).collect();
+                            #pre_set
+                            self.#field_access = Some(x);
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::VecPair => {
+                    let (k, v) = pair_types(arg);
+                    let setter_name =
+                        Ident::new(&format!("{}_insert", setter_name), Span::call_site());
+                    quote! {
+                        pub fn #setter_name(mut self, k: #k, v: #v) -> Self {
+                            self.#field_access.push((k, v));
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::Cell => {
+                    let arg = arg.expect("Cell setter requires a generic argument");
+                    quote! {
+                        pub fn #setter_name(mut self, x: #arg) -> Self {
+                            #pre_set
+                            self.#field_access.set(x);
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::CowStr => {
+                    // borrows `&'a str`, owns `String`, or takes a `Cow` as is
+                    quote! {
+                        pub fn #setter_name(
+                            mut self,
+                            x: impl Into<::std::borrow::Cow<#arg, str>>,
+                        ) -> Self {
+                            let x: ::std::borrow::Cow<#arg, str> = x.into();
+                            #pre_set
+                            self.#field_access = x;
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::SharedLock => {
+                    let arg = arg.expect("SharedLock setter requires a generic argument");
+                    let Some(GenericArgument::Type(lock)) = (match field_type {
+                        Type::Path(x) => x.path.segments.last().and_then(|x| nth_generic_arg(x, 0)),
+                        _ => None,
+                    }) else {
+                        panic!("SharedLock setter requires a lock type");
+                    };
+                    let setter_name =
+                        Ident::new(&format!("{}_inner", setter_name), Span::call_site());
+                    quote! {
+                        pub fn #setter_name(mut self, x: #arg) -> Self {
+                            self.#field_access = <#field_type>::new(<#lock>::new(x));
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::Shared => {
+                    let arg = arg.expect("Shared setter requires a generic argument");
+                    let setter_name =
+                        Ident::new(&format!("{}_inner", setter_name), Span::call_site());
+                    // `String` takes anything string-like
+                    let (ty, value) = match arg {
+                        GenericArgument::Type(Type::Path(x))
+                            if x.path.segments.last().is_some_and(|x| x.ident == "String") =>
+                        {
+                            (
+                                quote! { impl Into<String> },
+                                quote! { <#field_type>::new(x.into()) },
+                            )
+                        }
+                        _ => (quote! { #arg }, quote! { <#field_type>::new(x) }),
+                    };
+                    quote! {
+                        pub fn #setter_name(mut self, x: #ty) -> Self {
+                            self.#field_access = #value;
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::RefCell => {
+                    quote! {
+                        pub fn #setter_name(mut self, x: #arg) -> Self {
+                            #pre_set
+                            *self.#field_access.get_mut() = x;
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::WeakRc => {
+                    let arg = arg.expect("Weak setter requires a generic argument");
+                    let setter_name =
+                        Ident::new(&format!("{}_from", setter_name), Span::call_site());
+                    quote! {
+                        pub fn #setter_name(mut self, x: &::std::rc::Rc<#arg>) -> Self {
+                            self.#field_access = ::std::rc::Rc::downgrade(x);
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::WeakArc => {
+                    let arg = arg.expect("Weak setter requires a generic argument");
+                    let setter_name =
+                        Ident::new(&format!("{}_from", setter_name), Span::call_site());
+                    quote! {
+                        pub fn #setter_name(mut self, x: &::std::sync::Arc<#arg>) -> Self {
+                            self.#field_access = ::std::sync::Arc::downgrade(x);
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::ResultOk => {
+                    let arg = arg.expect("Result setter requires a generic argument");
+                    // `with_x` itself with `ok_wrap`, next to the raw `with_x` otherwise
+                    let setter_name = match rules.ok_wrap {
+                        true => setter_name,
+                        false => Ident::new(&format!("{}_ok", setter_name), Span::call_site()),
+                    };
+                    quote! {
+                        pub fn #setter_name(mut self, x: #arg) -> Self {
+                            self.#field_access = Ok(x);
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::ResultErr => {
+                    let arg = arg.expect("Result setter requires a generic argument");
+                    let setter_name =
+                        Ident::new(&format!("{}_err", setter_name), Span::call_site());
+                    quote! {
+                        pub fn #setter_name(mut self, x: #arg) -> Self {
+                            self.#field_access = Err(x);
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::Lock => {
+                    let arg = arg.expect("Lock setter requires a generic argument");
+                    let setter_name =
+                        Ident::new(&format!("{}_inner", setter_name), Span::call_site());
+                    quote! {
+                        pub fn #setter_name(mut self, x: #arg) -> Self {
+                            self.#field_access = <#field_type>::new(x);
+                            #post_set
+                            self
                         }
                     }
                 }
-            }
-            ty => {
-                // setter
-                generate(
-                    field,
-                    &rules,
-                    idx,
-                    None,
-                    &mut codes,
-                    Fns::Setter(Tys::Basic),
-                );
+                Tys::Array1 => {
+                    let arg = arg.expect("Array1 setter requires a generic argument");
+                    let setter_name_vec =
+                        Ident::new(&format!("{}_vec", setter_name), Span::call_site());
+                    quote! {
+                        pub fn #setter_name(mut self, x: &[#arg]) -> Self
+                        where
+                            for<'__aksr> #arg: Clone,
+                        {
+                            self.#field_access = ::ndarray::Array1::from(x.to_vec());
+                            #post_set
+                            self
+                        }
 
-                // getter
-                match ty {
-                    Type::Reference(_) => {
-                        // &'a T or &'a mut T
-                        generate(
-                            field,
-                            &rules,
-                            idx,
-                            None,
-                            &mut codes,
-                            Fns::Getter(Tys::Basic),
-                        );
-                    }
-                    Type::Array(_) | Type::Tuple(_) => {
-                        // array [T; n] and tuple (A, B, C, String)
-                        generate(field, &rules, idx, None, &mut codes, Fns::Getter(Tys::Ref));
+                        pub fn #setter_name_vec(mut self, x: Vec<#arg>) -> Self {
+                            self.#field_access = ::ndarray::Array1::from(x);
+                            #post_set
+                            self
+                        }
                     }
-                    _ => {
-                        // TODO: others
-                        generate(field, &rules, idx, None, &mut codes, Fns::Getter(Tys::Ref));
+                }
+                Tys::Array2 => {
+                    let arg = arg.expect("Array2 setter requires a generic argument");
+                    let setter_name =
+                        Ident::new(&format!("try_{}", setter_name), Span::call_site());
+                    quote! {
+                        pub fn #setter_name(
+                            mut self,
+                            shape: (usize, usize),
+                            x: Vec<#arg>,
+                        ) -> Result<Self, ::ndarray::ShapeError> {
+                            self.#field_access = ::ndarray::Array2::from_shape_vec(shape, x)?;
+                            #post_set
+                            Ok(self)
+                        }
                     }
                 }
-            }
-        }
-    }
-
-    // token stream
-    quote! {
-        #codes
-    }
-}
-
-fn generate(
-    field: &Field,
-    rules: &Rules,
-    idx: usize,
-    arg: Option<&GenericArgument>,
-    codes: &mut proc_macro2::TokenStream,
-    fn_type: Fns,
-) {
-    // setter_name & getter_name
-    let (setter_name, getter_name) = rules.generate_setter_getter_names(field, idx); // (move inside????)
-
-    // attrs
-    let field_type = &field.ty;
-    let field_name = field.ident.as_ref();
-    let field_index = Index::from(idx);
-    let field_access = field_name.map_or_else(|| quote! { #field_index }, |name| quote! { #name });
-
-    // token stream
-    let code = match fn_type {
-        Fns::Setter(ty) => {
-            if !rules.gen_setter {
-                return;
-            }
-            match ty {
-                Tys::Basic => {
+                Tys::Vector => {
+                    // Vec3, DVec2, Vector4<T>, ...
+                    let ty_name = match field_type {
+                        Type::Path(x) => x.path.segments.last().map(|x| x.ident.to_string()),
+                        _ => None,
+                    }
+                    .expect("Vector setter requires a path type");
+                    let n: usize = ty_name[ty_name.len() - 1..].parse().expect("vector length");
+                    let scalar = match (arg, &ty_name[..1]) {
+                        (Some(arg), _) => quote! { #arg },
+                        (None, "D") => quote! { f64 },
+                        (None, "I") => quote! { i32 },
+                        (None, "U") => quote! { u32 },
+                        (None, _) => quote! { f32 },
+                    };
+                    let components: Vec<_> = ["x", "y", "z", "w"][..n]
+                        .iter()
+                        .map(|x| Ident::new(x, Span::call_site()))
+                        .collect();
+                    let setter_name_components = Ident::new(
+                        &format!("{}_{}", setter_name, &"xyzw"[..n]),
+                        Span::call_site(),
+                    );
+                    let setter_name_array =
+                        Ident::new(&format!("{}_array", setter_name), Span::call_site());
                     quote! {
-                        pub fn #setter_name(mut self, x: #field_type) -> Self {
-                            self.#field_access = x;
+                        pub fn #setter_name_components(mut self, #(#components: #scalar),*) -> Self {
+                            self.#field_access = <#field_type>::new(#(#components),*);
+                            #post_set
+                            self
+                        }
+
+                        pub fn #setter_name_array(mut self, x: [#scalar; #n]) -> Self {
+                            self.#field_access = <#field_type>::from(x);
+                            #post_set
                             self
                         }
                     }
                 }
-                Tys::String => {
+                Tys::DateTime => {
+                    let setter_name =
+                        Ident::new(&format!("try_{}", setter_name), Span::call_site());
                     quote! {
-                        pub fn #setter_name(mut self, x: &str) -> Self {
-                            self.#field_access = x.to_string();
-                            self
+                        pub fn #setter_name(mut self, x: &str) -> Result<Self, ::chrono::ParseError> {
+                            self.#field_access = x.parse::<#field_type>()?;
+                            #post_set
+                            Ok(self)
                         }
                     }
                 }
-                Tys::Vec => {
-                    let arg = arg.expect("Vec setter requires a generic argument");
+                Tys::OffsetDateTime => {
+                    let setter_name =
+                        Ident::new(&format!("try_{}", setter_name), Span::call_site());
                     quote! {
-                        pub fn #setter_name(mut self, x: &[#arg]) -> Self {
-                            self.#field_access = x.to_vec();
-                            self
+                        pub fn #setter_name(mut self, x: &str) -> Result<Self, ::time::error::Parse> {
+                            self.#field_access = <#field_type>::parse(
+                                x,
+                                &::time::format_description::well_known::Rfc3339,
+                            )?;
+                            #post_set
+                            Ok(self)
                         }
                     }
                 }
-                Tys::VecInc if rules.inc_for_vec => {
-                    let arg = arg.expect("VecInc setter requires a generic argument");
-                    let setter_name = Ident::new(
-                        &format!("{}_{}", setter_name, INC_FOR_VEC),
+                Tys::Url | Tys::OptionUrl => {
+                    let setter_name =
+                        Ident::new(&format!("try_{}", setter_name), Span::call_site());
+                    let value = match ty {
+                        Tys::Url => quote! { ::url::Url::parse(x)? },
+                        _ => quote! { Some(::url::Url::parse(x)?) },
+                    };
+                    quote! {
+                        pub fn #setter_name(mut self, x: &str) -> Result<Self, ::url::ParseError> {
+                            self.#field_access = #value;
+                            #post_set
+                            Ok(self)
+                        }
+                    }
+                }
+                Tys::Regex | Tys::OptionRegex => {
+                    let setter_name =
+                        Ident::new(&format!("try_{}", setter_name), Span::call_site());
+                    let value = match ty {
+                        Tys::Regex => quote! { <#field_type>::new(x)? },
+                        _ => quote! { Some(::regex::Regex::new(x)?) },
+                    };
+                    quote! {
+                        pub fn #setter_name(mut self, x: &str) -> Result<Self, ::regex::Error> {
+                            self.#field_access = #value;
+                            #post_set
+                            Ok(self)
+                        }
+                    }
+                }
+                Tys::TakeFrom => {
+                    let fn_name = Ident::new(
+                        &format!("take_{}_from", rules.base_name(field, idx)),
                         Span::call_site(),
                     );
+                    // wipe the replaced secret instead of just dropping it
+                    let wipe = if rules.zeroize {
+                        quote! { ::zeroize::Zeroize::zeroize(&mut self.#field_access); }
+                    } else {
+                        quote! {}
+                    };
                     quote! {
-                        pub fn #setter_name(mut self, x: &[#arg]) -> Self {
-                            if self.#field_access.is_empty() {
-                                self.#field_access = Vec::from(x);
-                            } else {
-                                self.#field_access.extend_from_slice(x);
-                            }
+                        pub fn #fn_name(&mut self, other: &mut Self) -> &mut Self {
+                            #wipe
+                            self.#field_access = ::std::mem::take(&mut other.#field_access);
+                            #post_set
                             self
                         }
                     }
                 }
-                Tys::VecString => {
+                Tys::Validate => {
+                    let validate = &rules.validate;
+                    let fn_name = Ident::new(&format!("try_{}", setter_name), Span::call_site());
                     quote! {
-                        pub fn #setter_name(mut self, x: &[&str]) -> Self {
-                            self.#field_access = x.iter().map(|s| s.to_string()).collect();
-                            self
+                        pub fn #fn_name(mut self, x: #field_type) -> Result<Self, String> {
+                            #pre_set
+                            #validate(&x)?;
+                            self.#field_access = x;
+                            #post_set
+                            Ok(self)
                         }
                     }
                 }
-                Tys::VecStringInc if rules.inc_for_vec => {
-                    let setter_name = Ident::new(
-                        &format!("{}_{}", setter_name, INC_FOR_VEC),
+                Tys::TryInto => {
+                    let fn_name = Ident::new(&format!("try_{}", setter_name), Span::call_site());
+                    quote! {
+                        pub fn #fn_name<V: ::core::convert::TryInto<#field_type>>(
+                            mut self,
+                            x: V,
+                        ) -> Result<Self, V::Error> {
+                            let x = x.try_into()?;
+                            #pre_set
+                            self.#field_access = x;
+                            #post_set
+                            Ok(self)
+                        }
+                    }
+                }
+                Tys::NonZero => {
+                    let raw = nonzero_raw(&field.ty).unwrap();
+                    let fn_name = Ident::new(&format!("try_{}", setter_name), Span::call_site());
+                    quote! {
+                        pub fn #fn_name(
+                            mut self,
+                            x: #raw,
+                        ) -> Result<Self, ::core::num::TryFromIntError> {
+                            let x = <#field_type>::try_from(x)?;
+                            #pre_set
+                            self.#field_access = x;
+                            #post_set
+                            Ok(self)
+                        }
+                    }
+                }
+                Tys::ArraySlice => {
+                    let Type::Array(array) = &field.ty else {
+                        unreachable!()
+                    };
+                    let elem = &array.elem;
+                    let fn_name = Ident::new(&format!("try_{}", setter_name), Span::call_site());
+                    // `[T; N]: TryFrom<&[T]>` needs `T: Copy`, checked on use only
+                    quote! {
+                        pub fn #fn_name(
+                            mut self,
+                            x: &[#elem],
+                        ) -> Result<Self, ::core::array::TryFromSliceError>
+                        where
+                            for<'__aksr> #elem: Copy,
+                        {
+                            let x = <#field_type>::try_from(x)?;
+                            #pre_set
+                            self.#field_access = x;
+                            #post_set
+                            Ok(self)
+                        }
+                    }
+                }
+                Tys::FromStr => {
+                    let fn_name =
+                        Ident::new(&format!("try_{}_str", setter_name), Span::call_site());
+                    let (ty, value) = match arg {
+                        Some(arg) => (quote! { #arg }, quote! { Some(x) }),
+                        None => (quote! { #field_type }, quote! { x }),
+                    };
+                    quote! {
+                        pub fn #fn_name(
+                            mut self,
+                            x: &str,
+                        ) -> Result<Self, <#ty as ::core::str::FromStr>::Err> {
+                            let x = x.parse::<#ty>()?;
+                            #pre_set
+                            self.#field_access = #value;
+                            #post_set
+                            Ok(self)
+                        }
+                    }
+                }
+                Tys::Range => {
+                    let (range, text) = rules.range.as_ref().unwrap();
+                    let map = rules.map.as_ref().map(|map| quote! { let x = #map(x); });
+                    let name = rules.base_name(field, idx);
+                    let fn_name = Ident::new(&format!("try_{}", setter_name), Span::call_site());
+                    quote! {
+                        pub fn #fn_name(mut self, x: #field_type) -> Result<Self, String> {
+                            #map
+                            if !(#range).contains(&x) {
+                                return Err(format!("`{}` out of range {}", #name, #text));
+                            }
+                            self.#field_access = x;
+                            #post_set
+                            Ok(self)
+                        }
+                    }
+                }
+                Tys::Zeroize => {
+                    let fn_name = Ident::new(
+                        &format!("zeroize_{}", rules.base_name(field, idx)),
                         Span::call_site(),
                     );
                     quote! {
-                        pub fn #setter_name(mut self, x: &[&str]) -> Self {
-                            if self.#field_access.is_empty() {
-                                self.#field_access = x.iter().map(|s| s.to_string()).collect();
-                            } else {
-                                let mut x = x.iter().map(|s| s.to_string()).collect::<Vec<_>>();
-                                self.#field_access.append(&mut x);
-                            }
+                        pub fn #fn_name(&mut self) -> &mut Self {
+                            ::zeroize::Zeroize::zeroize(&mut self.#field_access);
                             self
                         }
                     }
                 }
-                Tys::Option => {
+                Tys::PathBuf => {
                     quote! {
-                        pub fn #setter_name(mut self, x: #arg) -> Self {
+                        pub fn #setter_name(mut self, x: impl AsRef<::std::path::Path>) -> Self {
+                            let x = x.as_ref().to_path_buf();
+                            #pre_set
+                            self.#field_access = x;
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::OptionPathBuf => {
+                    quote! {
+                        pub fn #setter_name(mut self, x: impl AsRef<::std::path::Path>) -> Self {
+                            let x = x.as_ref().to_path_buf();
+                            #pre_set
                             self.#field_access = Some(x);
+                            #post_set
                             self
                         }
                     }
                 }
-                Tys::OptionVec => {
-                    let arg = arg.expect("OptionVec setter requires a generic argument");
+                Tys::OsString => {
                     quote! {
-                        pub fn #setter_name(mut self, x: &[#arg]) -> Self {
-                            self.#field_access = Some(x.to_vec());
+                        pub fn #setter_name(mut self, x: impl AsRef<::std::ffi::OsStr>) -> Self {
+                            let x = x.as_ref().to_os_string();
+                            #pre_set
+                            self.#field_access = x;
+                            #post_set
                             self
                         }
                     }
                 }
-                Tys::OptionVecString => {
+                Tys::OptionOsString => {
                     quote! {
-                        pub fn #setter_name(mut self, x: &[&str]) -> Self {
-                            self.#field_access = Some(x.iter().map(|s| s.to_string()).collect());
+                        pub fn #setter_name(mut self, x: impl AsRef<::std::ffi::OsStr>) -> Self {
+                            let x = x.as_ref().to_os_string();
+                            #pre_set
+                            self.#field_access = Some(x);
+                            #post_set
+                            self
+                        }
+                    }
+                }
+                Tys::CString => {
+                    quote! {
+                        pub fn #setter_name(mut self, x: &::std::ffi::CStr) -> Self {
+                            let x = x.to_owned();
+                            #pre_set
+                            self.#field_access = x;
+                            #post_set
                             self
                         }
                     }
                 }
                 Tys::OptionString => {
                     quote! {
-                        pub fn #setter_name(mut self, x: &str) -> Self {
-                            self.#field_access = Some(x.to_string());
+                        pub fn #setter_name(mut self, x: impl Into<String>) -> Self {
+                            let x = x.into();
+                            #pre_set
+                            self.#field_access = Some(x);
+                            #post_set
                             self
                         }
                     }
@@ -652,71 +3559,478 @@ fn generate(
                 return;
             }
             match ty {
-                Tys::Basic => {
-                    quote! {
-                        pub fn #getter_name(&self) -> #field_type {
-                            self.#field_access
+                // secret: only redacted, and exposed on explicit opt-in
+                ty if rules.secret => {
+                    match getter_parts(&ty, field_type, arg, &field_access, &quote! {}) {
+                        Some((ret, body)) => {
+                            let getter_name_redacted =
+                                Ident::new(&format!("{}_redacted", getter_name), Span::call_site());
+                            let getter_name_exposed =
+                                Ident::new(&format!("{}_exposed", getter_name), Span::call_site());
+                            let exposed = if rules.expose {
+                                quote! {
+                                    pub fn #getter_name_exposed(&self) -> #ret {
+                                        #body
+                                    }
+                                }
+                            } else {
+                                quote! {}
+                            };
+                            quote! {
+                                pub fn #getter_name_redacted(&self) -> &'static str {
+                                    "***"
+                                }
+
+                                #exposed
+                            }
                         }
+                        None => quote! {},
                     }
                 }
-                Tys::Ref => {
+                Tys::VecPair => {
+                    let (k, v) = pair_types(arg);
+                    let getter_name =
+                        Ident::new(&format!("{}_get", getter_name), Span::call_site());
                     quote! {
-                        pub fn #getter_name(&self) -> &#field_type {
-                            &self.#field_access
+                        pub fn #getter_name<Q>(&self, k: &Q) -> Option<&#v>
+                        where
+                            #k: ::std::borrow::Borrow<Q>,
+                            Q: PartialEq + ?Sized,
+                        {
+                            self.#field_access
+                                .iter()
+                                .find(|(x, _)| ::std::borrow::Borrow::<Q>::borrow(x) == k)
+                                .map(|(_, v)| v)
                         }
                     }
                 }
-                Tys::String => {
+                Tys::Mutex | Tys::RwLock => {
+                    let arg = arg.expect("Lock getter requires a generic argument");
+                    let try_lock = match ty {
+                        Tys::Mutex => quote! { try_lock },
+                        _ => quote! { try_read },
+                    };
+                    let getter_name =
+                        Ident::new(&format!("{}_{}", getter_name, try_lock), Span::call_site());
                     quote! {
-                        pub fn #getter_name(&self) -> &str {
-                            &self.#field_access
+                        pub fn #getter_name(&self) -> Option<impl ::std::ops::Deref<Target = #arg> + '_> {
+                            self.#field_access.#try_lock().ok()
                         }
                     }
                 }
-                Tys::Vec => {
-                    let arg = arg.expect("Vec getter requires a generic argument");
+                Tys::Array1 | Tys::Array2 => {
+                    let arg = arg.expect("Array getter requires a generic argument");
+                    let view = match ty {
+                        Tys::Array1 => quote! { ::ndarray::ArrayView1<'_, #arg> },
+                        _ => quote! { ::ndarray::ArrayView2<'_, #arg> },
+                    };
                     quote! {
-                        pub fn #getter_name(&self) -> &[#arg] {
-                            &self.#field_access
+                        pub fn #getter_name(&self) -> #view {
+                            self.#field_access.view()
                         }
                     }
                 }
-                Tys::Option => {
-                    let arg = arg.expect("Option getter requires a generic argument");
+                Tys::Handle => {
+                    let getter_name =
+                        Ident::new(&format!("{}_handle", getter_name), Span::call_site());
                     quote! {
-                        pub fn #getter_name(&self) -> Option<#arg> {
-                            self.#field_access
+                        pub fn #getter_name(&self) -> #field_type {
+                            ::std::clone::Clone::clone(&self.#field_access)
                         }
                     }
                 }
-                Tys::OptionAsRef => {
-                    let arg = arg.expect("OptionAsRef getter requires a generic argument");
+                Tys::MapString => {
+                    let getter_name =
+                        Ident::new(&format!("{}_entries", getter_name), Span::call_site());
                     quote! {
-                        pub fn #getter_name(&self) -> Option<&#arg> {
-                            self.#field_access.as_ref()
+                        pub fn #getter_name(&self) -> impl Iterator<Item = (&str, &str)> {
+                            self.#field_access.iter().map(|(k, v)| (k.as_str(), v.as_str()))
                         }
                     }
                 }
-                Tys::OptionString => {
+                Tys::VecString => {
+                    let getter_name =
+                        Ident::new(&format!("{}_strs", getter_name), Span::call_site());
                     quote! {
-                        pub fn #getter_name(&self) -> Option<&str> {
-                            self.#field_access.as_deref()
+                        pub fn #getter_name(&self) -> impl Iterator<Item = &str> {
+                            self.#field_access.iter().map(String::as_str)
                         }
                     }
                 }
-                Tys::OptionVec => {
-                    let arg = arg.expect("OptionVec getter requires a generic argument");
-                    quote! {
-                        pub fn #getter_name(&self) -> Option<&[#arg]> {
-                            self.#field_access.as_deref()
+                ty => match getter_parts(&ty, field_type, arg, &field_access, &quote! {}) {
+                    Some((ret, body)) => quote! {
+                        pub fn #getter_name(&self) -> #ret {
+                            #body
                         }
-                    }
-                }
-                _ => quote! {},
+                    },
+                    None => quote! {},
+                },
+            }
+        }
+    };
+
+    // debug builds only: log old and new values, never for secrets
+    let code = if is_setter && rules.debug_log && !rules.secret {
+        let name = rules.base_name(field, idx);
+        with_stmts(
+            code,
+            quote! {
+                #[cfg(debug_assertions)]
+                let __aksr_old = format!("{:?}", self.#field_access);
+            },
+            quote! {
+                #[cfg(debug_assertions)]
+                eprintln!("[aksr] {}: {} -> {:?}", #name, __aksr_old, self.#field_access);
+            },
+        )
+    } else {
+        code
+    };
+
+    // trace events of setters, through `tracing` or `log`
+    let code = if is_setter && rules.trace {
+        let name = rules.base_name(field, idx);
+        let value = if rules.secret {
+            quote! { "<redacted>" }
+        } else {
+            quote! { format!("{:?}", self.#field_access) }
+        };
+        let event = if cfg!(feature = "tracing") {
+            quote! { ::tracing::trace!(field = #name, value = %#value, "aksr setter"); }
+        } else if cfg!(feature = "log") {
+            quote! { ::log::trace!("{} = {}", #name, #value); }
+        } else {
+            panic!("Builder(aksr): `trace` requires the `log` or `tracing` feature of aksr");
+        };
+        with_stmts(code, quote! {}, event)
+    } else {
+        code
+    };
+
+    // `&mut self` setters, `map_x` keeps consuming `self` to move the value out
+    let code = match is_setter && rules.by_ref() && !is_map_fn {
+        true => with_by_ref(code),
+        false => code,
+    };
+
+    // conditional twin of the assigning setter: `with_x_if(cond, x)`
+    let code = match is_assigning && rules.gen_setter {
+        true => with_if(code),
+        false => code,
+    };
+
+    // docs flagging sensitive fields
+    let code = if rules.secret && !struct_rules.skip_docs {
+        let doc = format!(
+            " **Sensitive**: `{}` holds secret data, avoid logging or displaying it.",
+            rules.base_name(field, idx)
+        );
+        with_attrs(code, &[syn::parse_quote! { #[doc = #doc] }])
+    } else {
+        code
+    };
+
+    // method names may leave snake case
+    let code = match &rules.rename_all {
+        Some(case) if case != "snake_case" => {
+            with_attrs(code, &[syn::parse_quote! { #[allow(non_snake_case)] }])
+        }
+        _ => code,
+    };
+
+    // catch dropped builders and unused reads
+    let code = match rules.must_use {
+        (true, _) if is_setter => with_attrs(code, &[syn::parse_quote! { #[must_use] }]),
+        (_, true) if !is_setter => with_attrs(code, &[syn::parse_quote! { #[must_use] }]),
+        _ => code,
+    };
+
+    // inline policy
+    let code = match &rules.inline {
+        Some(attr) => with_attrs(code, std::slice::from_ref(attr)),
+        None => code,
+    };
+
+    // struct level visibility
+    let code = match struct_rules.vis(is_setter) {
+        Some(vis) => with_vis(code, vis),
+        None => code,
+    };
+
+    // find renamed accessors under the field name as well
+    let code = match (&rules.alias, &field.ident) {
+        (Some(_), Some(ident)) if !struct_rules.skip_docs => {
+            let mut aliases = vec![ident.to_string()];
+            if is_setter {
+                aliases.push(format!("{}_{}", rules.prefix_setter, ident));
             }
+            let attrs = aliases
+                .iter()
+                .map(|x| syn::parse_quote! { #[doc(alias = #x)] })
+                .collect::<Vec<Attribute>>();
+            with_attrs(code, &attrs)
         }
+        _ => code,
     };
 
     // append
     codes.extend(code);
 }
+
+/// Add statements to the start and, before the returned value, to the end of every method in `code`
+fn with_stmts(
+    code: proc_macro2::TokenStream,
+    head: proc_macro2::TokenStream,
+    tail: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let head: syn::Block = syn::parse_quote! {{ #head }};
+    let tail: syn::Block = syn::parse_quote! {{ #tail }};
+    let mut item: ItemImpl = syn::parse_quote! { impl __ { #code } };
+    for impl_item in item.items.iter_mut() {
+        if let ImplItem::Fn(f) = impl_item {
+            let stmts = &mut f.block.stmts;
+            let ret = stmts.pop();
+            stmts.splice(0..0, head.stmts.iter().cloned());
+            stmts.extend(tail.stmts.iter().cloned());
+            stmts.extend(ret);
+        }
+    }
+    let items = item.items;
+
+    quote! { #(#items)* }
+}
+
+/// Add `xxx_if(self, cond, ..)` next to every method in `code`, calling it only when `cond` holds
+fn with_if(code: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let item: ItemImpl = syn::parse_quote! { impl __ { #code } };
+    let mut items = Vec::new();
+    for impl_item in item.items {
+        if let ImplItem::Fn(f) = &impl_item {
+            let name = &f.sig.ident;
+            let args = f.sig.inputs.iter().filter_map(|x| match x {
+                syn::FnArg::Typed(x) => Some(&x.pat),
+                syn::FnArg::Receiver(_) => None,
+            });
+            let body = quote! {{
+                if cond {
+                    self.#name(#(#args),*)
+                } else {
+                    self
+                }
+            }};
+            let mut g = f.clone();
+            g.sig.ident = Ident::new(&format!("{}_if", name), Span::call_site());
+            g.sig.inputs.insert(1, syn::parse_quote! { cond: bool });
+            g.block = syn::parse2(body).expect("conditional setter body");
+            items.push(impl_item.clone());
+            items.push(ImplItem::Fn(g));
+        } else {
+            items.push(impl_item);
+        }
+    }
+
+    quote! { #(#items)* }
+}
+
+/// Turn consuming methods in `code` into `&mut self` ones, `Self` in return types into `&mut Self`
+fn with_by_ref(code: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    fn by_ref(ty: &mut Type) {
+        match ty {
+            Type::Path(x) if x.path.is_ident("Self") => *ty = syn::parse_quote! { &mut Self },
+            Type::Path(x) => {
+                for segment in x.path.segments.iter_mut() {
+                    if let PathArguments::AngleBracketed(args) = &mut segment.arguments {
+                        for arg in args.args.iter_mut() {
+                            if let GenericArgument::Type(ty) = arg {
+                                by_ref(ty);
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut item: ItemImpl = syn::parse_quote! { impl __ { #code } };
+    for impl_item in item.items.iter_mut() {
+        if let ImplItem::Fn(f) = impl_item {
+            match f.sig.inputs.first_mut() {
+                Some(syn::FnArg::Receiver(receiver)) if receiver.reference.is_none() => {
+                    *receiver = syn::parse_quote! { &mut self };
+                }
+                _ => continue,
+            }
+            if let syn::ReturnType::Type(_, ty) = &mut f.sig.output {
+                by_ref(ty);
+            }
+        }
+    }
+    let items = item.items;
+
+    quote! { #(#items)* }
+}
+
+/// Split `code` into (others, conditional setters made by `with_if`)
+fn split_conditional(
+    code: proc_macro2::TokenStream,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let item: ItemImpl = syn::parse_quote! { impl __ { #code } };
+    let (conditional, others): (Vec<_>, Vec<_>) = item.items.into_iter().partition(|impl_item| {
+        matches!(impl_item, ImplItem::Fn(f)
+                if f.sig.ident.to_string().ends_with("_if")
+                    && matches!(f.sig.inputs.iter().nth(1), Some(syn::FnArg::Typed(x))
+                        if matches!(&*x.pat, syn::Pat::Ident(p) if p.ident == "cond")))
+    });
+
+    (quote! { #(#others)* }, quote! { #(#conditional)* })
+}
+
+/// Whether `code` has a method called `name`
+fn defines_fn(code: &proc_macro2::TokenStream, name: &str) -> bool {
+    let item: ItemImpl = syn::parse_quote! { impl __ { #code } };
+    item.items
+        .iter()
+        .any(|impl_item| matches!(impl_item, ImplItem::Fn(f) if f.sig.ident == name))
+}
+
+/// Add outer attributes to every method in `code`
+fn with_attrs(code: proc_macro2::TokenStream, attrs: &[Attribute]) -> proc_macro2::TokenStream {
+    let mut item: ItemImpl = syn::parse_quote! { impl __ { #code } };
+    for impl_item in item.items.iter_mut() {
+        if let ImplItem::Fn(f) = impl_item {
+            f.attrs.extend(attrs.iter().cloned());
+        }
+    }
+    let items = item.items;
+
+    quote! { #(#items)* }
+}
+
+/// Replace the visibility of every method in `code`
+fn with_vis(code: proc_macro2::TokenStream, vis: &syn::Visibility) -> proc_macro2::TokenStream {
+    let mut item: ItemImpl = syn::parse_quote! { impl __ { #code } };
+    for impl_item in item.items.iter_mut() {
+        if let ImplItem::Fn(f) = impl_item {
+            f.vis = vis.clone();
+        }
+    }
+    let items = item.items;
+
+    quote! { #(#items)* }
+}
+
+/// `krate::..::Xxx`, spelled out with the crate name
+fn from_crate(type_path: &syn::TypePath, krate: &str) -> bool {
+    let segments = &type_path.path.segments;
+    segments.len() > 1 && segments[0].ident == krate
+}
+
+/// `Xxx<T, ..>` rather than a user type of the same name without type arguments
+fn has_type_arg(segment: &PathSegment) -> bool {
+    matches!(nth_generic_arg(segment, 0), Some(GenericArgument::Type(_)))
+}
+
+/// The nth generic argument of `Xxx<A, B, ...>`
+fn nth_generic_arg(segment: &PathSegment, n: usize) -> Option<&GenericArgument> {
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) => args.args.iter().nth(n),
+        _ => None,
+    }
+}
+
+/// `(K, V)` of `Vec<(K, V)>`
+fn pair_types(arg: Option<&GenericArgument>) -> (&Type, &Type) {
+    match arg {
+        Some(GenericArgument::Type(Type::Tuple(tuple))) if tuple.elems.len() == 2 => {
+            (&tuple.elems[0], &tuple.elems[1])
+        }
+        _ => panic!("VecPair requires a `(K, V)` generic argument"),
+    }
+}
+
+/// Return type and body of a getter, `lifetime` is used for borrowed return types
+fn getter_parts(
+    ty: &Tys,
+    field_type: &Type,
+    arg: Option<&GenericArgument>,
+    field_access: &proc_macro2::TokenStream,
+    lifetime: &proc_macro2::TokenStream,
+) -> Option<(proc_macro2::TokenStream, proc_macro2::TokenStream)> {
+    let parts = match ty {
+        Tys::Basic => (quote! { #field_type }, quote! { self.#field_access }),
+        Tys::Ref => (
+            quote! { &#lifetime #field_type },
+            quote! { &self.#field_access },
+        ),
+        Tys::String => (quote! { &#lifetime str }, quote! { &self.#field_access }),
+        Tys::NonZero => {
+            let raw = nonzero_raw(field_type).expect("NonZero getter requires a NonZero type");
+            (quote! { #raw }, quote! { self.#field_access.get() })
+        }
+        Tys::Vec => {
+            let arg = arg.expect("Vec getter requires a generic argument");
+            (quote! { &#lifetime [#arg] }, quote! { &self.#field_access })
+        }
+        Tys::Option => {
+            let arg = arg.expect("Option getter requires a generic argument");
+            (quote! { Option<#arg> }, quote! { self.#field_access })
+        }
+        Tys::OptionBox => {
+            let arg = arg.expect("OptionBox getter requires a generic argument");
+            (
+                quote! { Option<&#lifetime #arg> },
+                quote! { self.#field_access.as_deref() },
+            )
+        }
+        Tys::OptionAsRef => {
+            let arg = arg.expect("OptionAsRef getter requires a generic argument");
+            (
+                quote! { Option<&#lifetime #arg> },
+                quote! { self.#field_access.as_ref() },
+            )
+        }
+        Tys::Lazy => {
+            let arg = arg.expect("Lazy getter requires a generic argument");
+            (quote! { &#lifetime #arg }, quote! { &*self.#field_access })
+        }
+        Tys::Cell => {
+            let arg = arg.expect("Cell getter requires a generic argument");
+            (quote! { #arg }, quote! { self.#field_access.get() })
+        }
+        Tys::PathBuf => (
+            quote! { &#lifetime ::std::path::Path },
+            quote! { &self.#field_access },
+        ),
+        Tys::OptionPathBuf => (
+            quote! { Option<&#lifetime ::std::path::Path> },
+            quote! { self.#field_access.as_deref() },
+        ),
+        Tys::OsString => (
+            quote! { &#lifetime ::std::ffi::OsStr },
+            quote! { &self.#field_access },
+        ),
+        Tys::OptionOsString => (
+            quote! { Option<&#lifetime ::std::ffi::OsStr> },
+            quote! { self.#field_access.as_deref() },
+        ),
+        Tys::CString => (
+            quote! { &#lifetime ::std::ffi::CStr },
+            quote! { &self.#field_access },
+        ),
+        Tys::OptionString => (
+            quote! { Option<&#lifetime str> },
+            quote! { self.#field_access.as_deref() },
+        ),
+        Tys::OptionVec => {
+            let arg = arg.expect("OptionVec getter requires a generic argument");
+            (
+                quote! { Option<&#lifetime [#arg]> },
+                quote! { self.#field_access.as_deref() },
+            )
+        }
+        _ => return None,
+    };
+
+    Some(parts)
+}
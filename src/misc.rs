@@ -1,19 +1,304 @@
 use proc_macro2::{Ident, Span};
-use syn::{punctuated::Punctuated, Expr, Field, Lit, Meta, Token};
+use syn::{
+    braced,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Attribute, Data, DataStruct, DeriveInput, Expr, ExprRange, Field, Fields, FieldsNamed,
+    Generics, Lit, Meta, RangeLimits, Token, Visibility,
+};
 
 use crate::{
-    ALIAS, ARGS, GETTER, GETTER_PREFIX, GETTER_PREFIX_DEFAULT, INC_FOR_VEC, SETTER, SETTER_PREFIX,
-    SETTER_PREFIX_DEFAULT,
+    ALIAS, ALLOW, APPLY, ARGS, BOOL_GETTER, BUILD, BUILDER, CLAMP, DEBUG_INVARIANT, DEBUG_LOG,
+    DEFAULT, DEFAULT_SKIP, DOCS, EACH, EXPOSE, FFI, FIELD_ID, FROM_STR, GETTER, GETTER_PREFIX,
+    GETTER_PREFIX_DEFAULT, GETTER_VISIBILITY, HANDLE, IMPL_TRAIT, INC_FOR_VEC, INLINE, INTO,
+    INVARIANT, IS_DEFAULT, LAZY, MAP, MAP_WITH, MERGE_JSON, MODE, MUST_USE, NESTED, OK_WRAP,
+    ON_SET, PATCH, PUBLIC_ONLY, RANGE, RANGE_MODE, READONLY, REF_VIEW, RENAMED_FROM, RENAME_ALL,
+    REQUIRED, RESET, SECRET, SETTER, SETTERS, SETTER_PREFIX, SETTER_PREFIX_BY_REF,
+    SETTER_PREFIX_DEFAULT, SETTER_VISIBILITY, TAKE_FROM, TOGGLE, TRACE, TRACK_SET, TRY_INTO,
+    VALIDATE, VIEW, VIEW_TRAIT, VIEW_TRAIT_IMPL, VISIBILITY, WHEN, WRITEONLY, ZEROIZE,
 };
 
-#[derive(Debug)]
+/// Input of `builder! {}`: a named struct whose fields may carry `= default` values
+pub(crate) struct InlineStruct {
+    pub st: DeriveInput,
+    pub defaults: Vec<Option<Expr>>,
+}
+
+impl Parse for InlineStruct {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis: Visibility = input.parse()?;
+        let struct_token: Token![struct] = input.parse()?;
+        let ident: Ident = input.parse()?;
+        let mut generics: Generics = input.parse()?;
+        generics.where_clause = input.parse()?;
+
+        let content;
+        let brace_token = braced!(content in input);
+        let mut named = Punctuated::new();
+        let mut defaults = Vec::new();
+        while !content.is_empty() {
+            named.push(Field::parse_named(&content)?);
+            defaults.push(match content.parse::<Option<Token![=]>>()? {
+                Some(_) => Some(content.parse::<Expr>()?),
+                None => None,
+            });
+            if content.is_empty() {
+                break;
+            }
+            content.parse::<Token![,]>()?;
+        }
+
+        let st = DeriveInput {
+            attrs,
+            vis,
+            ident,
+            generics,
+            data: Data::Struct(DataStruct {
+                struct_token,
+                fields: Fields::Named(FieldsNamed { brace_token, named }),
+                semi_token: None,
+            }),
+        };
+
+        Ok(Self { st, defaults })
+    }
+}
+
+/// Struct level rules, from `#[args(...)]` or `#[builder(...)]` on the struct itself
+#[derive(Debug, Default)]
+pub(crate) struct StructRules {
+    pub gen_is_default: bool,
+    pub gen_view: bool,
+    pub gen_merge_json: bool,
+    pub gen_ffi: bool,
+    pub gen_field_id: bool,
+    pub skip_apply: bool, // `apply = false`
+    pub skip_when: bool,  // `when = false`
+    pub gen_build: bool,  // `build()` checking `validate`/`range` without required `Option` fields
+    pub gen_patch: bool,
+    pub gen_ref_view: bool,
+    pub view_trait: Option<(syn::Path, bool)>, // (trait, defined here)
+    pub impl_trait: Option<syn::Path>,
+    pub skip_default_impl: bool, // the `Default` impl comes from the attribute or inline macro
+    pub defaults: Option<Vec<Option<Expr>>>, // field defaults of `builder!`, `= value` included
+    pub prefix_setter: Option<String>,
+    pub prefix_getter: Option<String>,
+    pub vis: Option<Visibility>,
+    pub vis_getter: Option<Visibility>,
+    pub vis_setter: Option<Visibility>,
+    pub inline: Option<Attribute>,
+    pub inc_for_vec: bool,
+    pub rename_all: Option<String>,
+    pub skip_docs: bool,                            // `docs = false`
+    pub skip_getters: bool,                         // `mode = "setters"`
+    pub setters: Option<(bool, bool)>,              // `setters = "owned" | "mut" | "both"`
+    pub toggle: bool, // `enable_x` & `disable_x` for every `bool` field
+    pub bool_getter: Option<String>, // `is_x()` getters of `bool` fields
+    pub skip_setters: bool, // `mode = "getters"`
+    pub default_skip: bool, // fields opt in with `allow(getter, setter)`
+    pub public_only: bool, // private fields are skipped
+    pub must_use: (bool, bool), // (setters, getters)
+    pub invariant: Option<(syn::ExprPath, bool)>, // (check, debug only)
+    pub nested: Vec<(Vec<syn::Member>, syn::Type)>, // pass-through getters: `inner.width: f32`
+    pub tracker: Option<proc_macro2::TokenStream>, // access to the `#[args(track_set)]` field
+}
+
+impl StructRules {
+    /// visibility of generated getters or setters, `pub` when unset
+    pub fn vis(&self, is_setter: bool) -> Option<&Visibility> {
+        match is_setter {
+            true => self.vis_setter.as_ref().or(self.vis.as_ref()),
+            false => self.vis_getter.as_ref().or(self.vis.as_ref()),
+        }
+    }
+}
+
+impl From<&[Attribute]> for StructRules {
+    fn from(attrs: &[Attribute]) -> Self {
+        let mut rules = StructRules::default();
+        for attr in attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident(ARGS) || attr.path().is_ident(BUILDER))
+        {
+            let nested = match attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+            {
+                Ok(x) => x,
+                Err(err) => panic!("{}", err),
+            };
+            for meta in &nested {
+                let (ident, value, value_str) = match meta {
+                    Meta::Path(path) => (path.get_ident(), true, None),
+                    Meta::NameValue(name_value) => (
+                        name_value.path.get_ident(),
+                        Rules::parse_bool_or_str(&name_value.value),
+                        Rules::parse_str(&name_value.value),
+                    ),
+                    Meta::List(_) => continue,
+                };
+                match ident.map(|i| i.to_string()).as_deref() {
+                    Some(IS_DEFAULT) => rules.gen_is_default = value,
+                    Some(VIEW) => rules.gen_view = value,
+                    Some(MERGE_JSON) => rules.gen_merge_json = value,
+                    Some(FFI) => rules.gen_ffi = value,
+                    Some(FIELD_ID) => rules.gen_field_id = value,
+                    Some(APPLY) => rules.skip_apply = !value,
+                    Some(WHEN) => rules.skip_when = !value,
+                    Some(BUILD) => rules.gen_build = value,
+                    Some(PATCH) => rules.gen_patch = value,
+                    Some(REF_VIEW) => rules.gen_ref_view = value,
+                    Some(VIEW_TRAIT) => {
+                        rules.view_trait = match value_str.map(|x| syn::parse_str::<Ident>(&x)) {
+                            Some(Ok(x)) => Some((x.into(), true)),
+                            _ => panic!("Builder(aksr): `view_trait` expects a trait name, e.g. \"RectView\""),
+                        }
+                    }
+                    Some(IMPL_TRAIT) => {
+                        rules.impl_trait = match value_str.map(|x| syn::parse_str::<syn::Path>(&x)) {
+                            Some(Ok(x)) => Some(x),
+                            _ => panic!("Builder(aksr): `impl_trait` expects a trait path, e.g. \"crate::Configurable\""),
+                        }
+                    }
+                    Some(VIEW_TRAIT_IMPL) => {
+                        rules.view_trait = match value_str.map(|x| syn::parse_str::<syn::Path>(&x)) {
+                            Some(Ok(x)) => Some((x, false)),
+                            _ => panic!("Builder(aksr): `view_trait_impl` expects a trait path, e.g. \"crate::RectView\""),
+                        }
+                    }
+                    Some(SETTER_PREFIX) => rules.prefix_setter = value_str,
+                    Some(SETTERS) => rules.setters = Some(Rules::parse_setters(value_str)),
+                    Some(GETTER_PREFIX) => rules.prefix_getter = value_str,
+                    Some(INLINE) => rules.inline = Some(Rules::parse_inline(value_str)),
+                    Some(INC_FOR_VEC) => rules.inc_for_vec = value,
+                    Some(DOCS) => rules.skip_docs = !value,
+                    Some(DEFAULT_SKIP) => rules.default_skip = value,
+                    Some(PUBLIC_ONLY) => rules.public_only = value,
+                    Some(TOGGLE) => rules.toggle = value,
+                    Some(BOOL_GETTER) => rules.bool_getter = value_str,
+                    Some(MUST_USE) => rules.must_use = Rules::parse_must_use(meta),
+                    Some(MODE) => match value_str.as_deref() {
+                        Some("getters") => (rules.skip_getters, rules.skip_setters) = (false, true),
+                        Some("setters") => (rules.skip_getters, rules.skip_setters) = (true, false),
+                        Some("both") => (rules.skip_getters, rules.skip_setters) = (false, false),
+                        _ => panic!(
+                            "Builder(aksr): `mode` expects one of \"getters\", \"setters\" or \"both\""
+                        ),
+                    },
+                    Some(RENAME_ALL) => match value_str.as_deref() {
+                        Some(
+                            "snake_case"
+                            | "camelCase"
+                            | "PascalCase"
+                            | "lowercase"
+                            | "UPPERCASE"
+                            | "SCREAMING_SNAKE_CASE",
+                        ) => rules.rename_all = value_str,
+                        _ => panic!(
+                            "Builder(aksr): unsupported `rename_all` case: {:?}",
+                            value_str.unwrap_or_default()
+                        ),
+                    },
+                    Some(x @ (VISIBILITY | GETTER_VISIBILITY | SETTER_VISIBILITY)) => {
+                        let vis = value_str
+                            .and_then(|x| syn::parse_str::<Visibility>(&x).ok())
+                            .unwrap_or_else(|| {
+                                panic!(
+                                    "Builder(aksr): `{}` expects a visibility, e.g. \"pub(crate)\"",
+                                    x
+                                )
+                            });
+                        match x {
+                            VISIBILITY => rules.vis = Some(vis),
+                            GETTER_VISIBILITY => rules.vis_getter = Some(vis),
+                            _ => rules.vis_setter = Some(vis),
+                        }
+                    }
+                    Some(x @ (INVARIANT | DEBUG_INVARIANT)) => {
+                        let path = value_str
+                            .and_then(|x| syn::parse_str::<syn::ExprPath>(&x).ok())
+                            .unwrap_or_else(|| {
+                                panic!(
+                                    "Builder(aksr): `{}` expects a path, e.g. \"Self::check\"",
+                                    x
+                                )
+                            });
+                        rules.invariant = Some((path, x == DEBUG_INVARIANT));
+                    }
+                    Some(NESTED) => {
+                        let parser = |input: syn::parse::ParseStream| {
+                            let path =
+                                Punctuated::<syn::Member, Token![.]>::parse_separated_nonempty(
+                                    input,
+                                )?;
+                            input.parse::<Token![:]>()?;
+                            let ty = input.parse::<syn::Type>()?;
+                            Ok((path.into_iter().collect::<Vec<_>>(), ty))
+                        };
+                        match value_str.map(|x| syn::parse::Parser::parse_str(parser, &x)) {
+                            Some(Ok((path, ty))) if path.len() > 1 => rules.nested.push((path, ty)),
+                            _ => panic!(
+                                "Builder(aksr): `nested` expects a field path and type, e.g. \"inner.width: f32\""
+                            ),
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        rules
+    }
+}
+
+#[derive(Debug, Clone)]
 pub(crate) struct Rules {
     pub alias: Option<Ident>,
     pub inc_for_vec: bool,
     pub prefix_setter: String,
     pub prefix_getter: String,
+    pub getter_prefixed: bool, // prefix named getters as well, `getter_prefix` given explicitly
     pub gen_getter: bool,
     pub gen_setter: bool,
+    pub setter_requested: bool, // `setter = true` given explicitly
+    pub track_set: bool,
+    pub ok_wrap: bool,
+    pub take_from: bool,
+    pub secret: bool, // redacted accessors only, memory is left as is
+    pub expose: bool,
+    pub zeroize: bool, // independent of `secret`: `zeroize_x()` & wiping `take_x_from`
+    pub clamp: Option<ExprRange>,
+    pub handle: bool,
+    pub renamed_from: Option<String>,
+    pub debug_log: bool,
+    pub writeonly: bool,
+    pub lazy: bool, // `Option<T>` wrapped by the attribute macro
+    pub inline: Option<Attribute>,
+    pub rename_all: Option<String>,
+    pub must_use: (bool, bool), // (setters, getters)
+    pub default: Option<Expr>,
+    pub required: bool,
+    pub validate: Option<syn::ExprPath>, // fn(&T) -> Result<(), String>
+    pub try_into: bool,
+    pub from_str: bool,
+    pub range: Option<(ExprRange, String)>,
+    pub range_mode: RangeMode,
+    pub map: Option<syn::ExprPath>,    // fn(T) -> T
+    pub on_set: Option<syn::ExprPath>, // fn(&mut Self)
+    pub trace: bool,
+    pub into: bool,
+    pub each: Option<String>,
+    pub reset: bool,
+    pub map_fn: bool, // `map_with`: `map_x(f)`
+    pub toggle: bool,
+    pub setters: (bool, bool), // (consuming `with_x`, `set_x(&mut self, x) -> &mut Self`)
+}
+
+/// What a setter does with a value outside of `#[args(range = "...")]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeMode {
+    Clamp,
+    Panic,
+    Try,
 }
 
 impl Default for Rules {
@@ -23,16 +308,77 @@ impl Default for Rules {
             inc_for_vec: false,
             prefix_setter: SETTER_PREFIX_DEFAULT.into(), // with, for all struct
             prefix_getter: GETTER_PREFIX_DEFAULT.into(), // nth, for unnamed struct
+            getter_prefixed: false,
             gen_getter: true,
             gen_setter: true,
+            setter_requested: false,
+            track_set: false,
+            ok_wrap: false,
+            take_from: false,
+            secret: false,
+            expose: false,
+            zeroize: false,
+            clamp: None,
+            handle: false,
+            renamed_from: None,
+            debug_log: false,
+            writeonly: false,
+            lazy: false,
+            inline: None,
+            rename_all: None,
+            must_use: (false, false),
+            default: None,
+            required: false,
+            validate: None,
+            try_into: false,
+            from_str: false,
+            range: None,
+            range_mode: RangeMode::Clamp,
+            map: None,
+            on_set: None,
+            trace: false,
+            into: false,
+            each: None,
+            reset: false,
+            map_fn: false,
+            toggle: false,
+            setters: (true, false),
         }
     }
 }
 
 impl From<&Field> for Rules {
     fn from(field: &Field) -> Self {
+        Self::new(field, &StructRules::default())
+    }
+}
+
+impl Rules {
+    /// Field rules on top of the struct level defaults
+    pub fn new(field: &Field, struct_rules: &StructRules) -> Self {
         let mut rules = Rules::default();
-        if let Some(attr) = &field.attrs.first() {
+        let mut prefix_setter = struct_rules.prefix_setter.is_some();
+        let mut prefix_getter = false;
+        let mut bool_getter = (struct_rules.bool_getter.clone(), false); // (prefix, explicit)
+        if let Some(prefix) = &struct_rules.prefix_setter {
+            rules.prefix_setter = prefix.clone();
+        }
+        rules.setters = struct_rules.setters.unwrap_or((true, false));
+        rules.inline = struct_rules.inline.clone();
+        rules.inc_for_vec = struct_rules.inc_for_vec;
+        let skipped = struct_rules.default_skip
+            || (struct_rules.public_only && !matches!(field.vis, Visibility::Public(_)));
+        rules.gen_getter = !struct_rules.skip_getters && !skipped;
+        rules.gen_setter = !struct_rules.skip_setters && !skipped;
+        rules.rename_all = struct_rules.rename_all.clone();
+        rules.must_use = struct_rules.must_use;
+        rules.toggle = struct_rules.toggle
+            && matches!(&field.ty, syn::Type::Path(x) if x.path.is_ident("bool"));
+        if let Some(prefix) = &struct_rules.prefix_getter {
+            rules.prefix_getter = prefix.clone();
+            rules.getter_prefixed = true;
+        }
+        for attr in &field.attrs {
             if attr.path().is_ident(ARGS) {
                 let nested =
                     match attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated) {
@@ -52,7 +398,8 @@ impl From<&Field> for Rules {
                                     rules.gen_getter = Self::parse_bool_or_str(&name_value.value)
                                 }
                                 Some(SETTER) => {
-                                    rules.gen_setter = Self::parse_bool_or_str(&name_value.value)
+                                    rules.gen_setter = Self::parse_bool_or_str(&name_value.value);
+                                    rules.setter_requested = rules.gen_setter;
                                 }
                                 Some(ALIAS) => {
                                     if let Expr::Lit(lit) = &name_value.value {
@@ -66,16 +413,124 @@ impl From<&Field> for Rules {
                                     if let Expr::Lit(lit) = &name_value.value {
                                         if let Lit::Str(x) = &lit.lit {
                                             rules.prefix_setter = x.value();
+                                            prefix_setter = true;
                                         }
                                     }
                                 }
+                                Some(SETTERS) => {
+                                    rules.setters =
+                                        Self::parse_setters(Self::parse_str(&name_value.value))
+                                }
                                 Some(GETTER_PREFIX) => {
                                     if let Expr::Lit(lit) = &name_value.value {
                                         if let Lit::Str(x) = &lit.lit {
                                             rules.prefix_getter = x.value();
+                                            rules.getter_prefixed = true;
+                                            prefix_getter = true;
                                         }
                                     }
                                 }
+                                Some(BOOL_GETTER) => {
+                                    bool_getter = match Self::parse_str(&name_value.value) {
+                                        Some(x) => (Some(x), true),
+                                        None => panic!("Builder(aksr): `bool_getter` expects a prefix, e.g. \"is\""),
+                                    }
+                                }
+                                Some(RENAMED_FROM) => {
+                                    if let Expr::Lit(lit) = &name_value.value {
+                                        if let Lit::Str(x) = &lit.lit {
+                                            rules.renamed_from = Some(x.value());
+                                        }
+                                    }
+                                }
+                                Some(CLAMP) => {
+                                    if let Expr::Lit(lit) = &name_value.value {
+                                        if let Lit::Str(x) = &lit.lit {
+                                            rules.clamp = match x.parse::<ExprRange>() {
+                                                Ok(range)
+                                                    if range.start.is_some()
+                                                        || range.end.is_some() =>
+                                                {
+                                                    if range.end.is_some()
+                                                        && !matches!(
+                                                            range.limits,
+                                                            RangeLimits::Closed(_)
+                                                        )
+                                                    {
+                                                        panic!("Builder(aksr): `clamp` range must be inclusive, e.g. \"0.0..=1.0\"");
+                                                    }
+                                                    Some(range)
+                                                }
+                                                _ => panic!(
+                                                    "Builder(aksr): invalid `clamp` range: {:?}",
+                                                    x.value()
+                                                ),
+                                            };
+                                        }
+                                    }
+                                }
+                                Some(MUST_USE) => rules.must_use = Self::parse_must_use(meta),
+                                Some(DEFAULT) => rules.default = Some(name_value.value.clone()),
+                                Some(VALIDATE) => {
+                                    rules.validate = match Self::parse_str(&name_value.value)
+                                        .map(|x| syn::parse_str::<syn::ExprPath>(&x))
+                                    {
+                                        Some(Ok(x)) => Some(x),
+                                        _ => panic!("Builder(aksr): `validate` expects a function path, e.g. \"check_port\""),
+                                    }
+                                }
+                                Some(MAP) => {
+                                    rules.map = match Self::parse_str(&name_value.value)
+                                        .map(|x| syn::parse_str::<syn::ExprPath>(&x))
+                                    {
+                                        Some(Ok(x)) => Some(x),
+                                        _ => panic!("Builder(aksr): `map` expects a function path, e.g. map = \"Self::normalize\""),
+                                    }
+                                }
+                                Some(EACH) => {
+                                    rules.each = match Self::parse_str(&name_value.value) {
+                                        Some(x) if syn::parse_str::<Ident>(&x).is_ok() => Some(x),
+                                        _ => panic!("Builder(aksr): `each` expects a method name, e.g. \"tag\""),
+                                    }
+                                }
+                                Some(ON_SET) => {
+                                    rules.on_set = match Self::parse_str(&name_value.value)
+                                        .map(|x| syn::parse_str::<syn::ExprPath>(&x))
+                                    {
+                                        Some(Ok(x)) => Some(x),
+                                        _ => panic!("Builder(aksr): `on_set` expects a function path, e.g. \"Self::recompute\""),
+                                    }
+                                }
+                                Some(RANGE) => {
+                                    rules.range = match Self::parse_str(&name_value.value) {
+                                        Some(x) => match syn::parse_str::<ExprRange>(&x) {
+                                            Ok(range)
+                                                if range.start.is_some()
+                                                    || range.end.is_some() =>
+                                            {
+                                                Some((range, x))
+                                            }
+                                            _ => panic!(
+                                                "Builder(aksr): invalid `range`: {:?}",
+                                                x
+                                            ),
+                                        },
+                                        None => panic!("Builder(aksr): `range` expects a string, e.g. \"0..=100\""),
+                                    }
+                                }
+                                Some(RANGE_MODE) => {
+                                    rules.range_mode =
+                                        match Self::parse_str(&name_value.value).as_deref() {
+                                            Some("clamp") => RangeMode::Clamp,
+                                            Some("panic") => RangeMode::Panic,
+                                            Some("try") => RangeMode::Try,
+                                            _ => panic!("Builder(aksr): `range_mode` expects one of \"clamp\", \"panic\" or \"try\""),
+                                        }
+                                }
+                                Some(INLINE) => {
+                                    rules.inline =
+                                        Some(Self::parse_inline(Self::parse_str(&name_value.value)))
+                                }
                                 Some(INC_FOR_VEC) => {
                                     if let Expr::Lit(lit) = &name_value.value {
                                         if let Lit::Bool(x) = &lit.lit {
@@ -86,17 +541,142 @@ impl From<&Field> for Rules {
                                 _ => {}
                             }
                         }
-                        Meta::Path(_) | Meta::List(_) => continue,
+                        Meta::Path(path) => {
+                            match path.get_ident().map(|i| i.to_string()).as_deref() {
+                                Some(TRACK_SET) => rules.track_set = true,
+                                Some(OK_WRAP) => rules.ok_wrap = true,
+                                Some(TAKE_FROM) => rules.take_from = true,
+                                Some(SECRET) => rules.secret = true,
+                                Some(EXPOSE) => rules.expose = true,
+                                Some(ZEROIZE) => rules.zeroize = true,
+                                Some(HANDLE) => rules.handle = true,
+                                Some(DEBUG_LOG) => rules.debug_log = true,
+                                Some(TRACE) => rules.trace = true,
+                                Some(INTO) => rules.into = true,
+                                Some(RESET) => rules.reset = true,
+                                Some(TOGGLE) => rules.toggle = true,
+                                Some(MAP_WITH) => rules.map_fn = true,
+                                Some(MAP) => panic!("Builder(aksr): `map` expects a function path, e.g. map = \"Self::normalize\"; use `map_with` for `map_x(f)`"),
+                                Some(LAZY) => rules.lazy = true,
+                                Some(REQUIRED) => rules.required = true,
+                                Some(TRY_INTO) => rules.try_into = true,
+                                Some(FROM_STR) => rules.from_str = true,
+                                Some(MUST_USE) => rules.must_use = Self::parse_must_use(meta),
+                                Some(DEFAULT) => {
+                                    rules.default = Some(
+                                        syn::parse_quote! { ::core::default::Default::default() },
+                                    )
+                                }
+                                Some(READONLY) => {
+                                    rules.gen_getter = true;
+                                    rules.gen_setter = false;
+                                }
+                                Some(WRITEONLY) => {
+                                    rules.gen_getter = false;
+                                    rules.gen_setter = true;
+                                    rules.setter_requested = true;
+                                    rules.writeonly = true;
+                                }
+                                _ => {}
+                            }
+                        }
+                        Meta::List(list) if list.path.is_ident(ALLOW) => {
+                            // opt in under `default_skip`: allow(getter, setter)
+                            let allowed = match list.parse_args_with(
+                                Punctuated::<syn::Path, Token![,]>::parse_terminated,
+                            ) {
+                                Ok(x) => x,
+                                Err(err) => panic!("{}", err),
+                            };
+                            for path in &allowed {
+                                match path.get_ident().map(|i| i.to_string()).as_deref() {
+                                    Some(GETTER) => rules.gen_getter = true,
+                                    Some(SETTER) => {
+                                        rules.gen_setter = true;
+                                        rules.setter_requested = true;
+                                    }
+                                    _ => panic!(
+                                        "Builder(aksr): `allow` expects `getter` and/or `setter`"
+                                    ),
+                                }
+                            }
+                        }
+                        Meta::List(_) => continue,
                     }
                 }
             }
         }
 
+        // `is_x()` getters of flags
+        if let (Some(prefix), explicit) = bool_getter {
+            let is_bool = matches!(&field.ty, syn::Type::Path(x) if x.path.is_ident("bool"));
+            if explicit && !is_bool {
+                panic!("Builder(aksr): `bool_getter` is only supported on `bool` fields");
+            }
+            if is_bool && !prefix_getter {
+                rules.prefix_getter = prefix;
+                rules.getter_prefixed = true;
+            }
+        }
+
+        // in-place setters read as `set_x` unless a prefix is given
+        if rules.setters == (false, true) && !prefix_setter {
+            rules.prefix_setter = SETTER_PREFIX_BY_REF.into();
+        }
+
         rules
     }
-}
 
-impl Rules {
+    /// `setters = "owned"`: consuming setters; `"mut"`: `&mut self` ones; `"both"`: `with_x` & `set_x`
+    pub fn parse_setters(value: Option<String>) -> (bool, bool) {
+        match value.as_deref() {
+            Some("owned") => (true, false),
+            Some("mut") => (false, true),
+            Some("both") => (true, true),
+            _ => panic!("Builder(aksr): `setters` expects one of \"owned\", \"mut\" or \"both\""),
+        }
+    }
+
+    /// setters take `&mut self` only
+    pub fn by_ref(&self) -> bool {
+        self.setters == (false, true)
+    }
+
+    pub fn parse_str(value: &Expr) -> Option<String> {
+        match value {
+            Expr::Lit(lit) => match &lit.lit {
+                Lit::Str(x) => Some(x.value()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// `#[inline]` attribute of an inline policy: "default", "always" or "never"
+    pub fn parse_inline(value: Option<String>) -> Attribute {
+        match value.as_deref() {
+            Some("default") => syn::parse_quote! { #[inline] },
+            Some("always") => syn::parse_quote! { #[inline(always)] },
+            Some("never") => syn::parse_quote! { #[inline(never)] },
+            _ => panic!(
+                "Builder(aksr): `inline` expects one of \"default\", \"always\" or \"never\""
+            ),
+        }
+    }
+
+    /// `must_use`: setters; `must_use = "all"`: setters & getters; `must_use = false`: none
+    pub fn parse_must_use(meta: &Meta) -> (bool, bool) {
+        match meta {
+            Meta::NameValue(name_value) => match Self::parse_str(&name_value.value).as_deref() {
+                Some("all") => (true, true),
+                Some("setters") => (true, false),
+                Some("getters") => (false, true),
+                _ => (Self::parse_bool_or_str(&name_value.value), false),
+            },
+            _ => (true, false),
+        }
+    }
+
     pub fn parse_bool_or_str(value: &Expr) -> bool {
         match value {
             Expr::Lit(lit) => match &lit.lit {
@@ -111,6 +691,15 @@ impl Rules {
         }
     }
 
+    /// alias, field name or index
+    pub fn base_name(&self, field: &Field, idx: usize) -> String {
+        match (&self.alias, &field.ident) {
+            (Some(alias), _) => alias.to_string(),
+            (None, Some(ident)) => ident.to_string(),
+            (None, None) => idx.to_string(),
+        }
+    }
+
     pub fn generate_setter_getter_names(&self, field: &Field, idx: usize) -> (Ident, Ident) {
         match &field.ident {
             None => {
@@ -139,15 +728,21 @@ impl Rules {
             }
             Some(ident) => {
                 // named: ident, alias
+                let ident = match &self.rename_all {
+                    Some(case) => rename_case(&ident.to_string(), case),
+                    None => ident.to_string(),
+                };
                 let setter_name = match &self.alias {
                     None => format!("{}_{}", self.prefix_setter, ident),
                     Some(alias) => format!("{}_{}", self.prefix_setter, alias),
                 };
                 let setter_name = Ident::new(&setter_name, Span::call_site());
 
-                let getter_name = match &self.alias {
-                    None => format!("{}", ident),
-                    Some(alias) => format!("{}", alias),
+                let getter_name = match (&self.alias, self.getter_prefixed) {
+                    (None, false) => ident,
+                    (Some(alias), false) => format!("{}", alias),
+                    (None, true) => format!("{}_{}", self.prefix_getter, ident),
+                    (Some(alias), true) => format!("{}_{}", self.prefix_getter, alias),
                 };
                 let getter_name = Ident::new(&getter_name, Span::call_site());
                 (setter_name, getter_name)
@@ -156,11 +751,67 @@ impl Rules {
     }
 }
 
+/// `RectF32` -> `rect_f32`
+pub(crate) fn to_snake_case(s: &str) -> String {
+    let mut out = String::new();
+    let mut prev_lower = false;
+    for c in s.chars() {
+        if c.is_uppercase() {
+            if prev_lower {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+            prev_lower = false;
+        } else {
+            out.push(c);
+            prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        }
+    }
+
+    out
+}
+
+/// `maxWidth` -> `max_width`, `maxWidth`, `MaxWidth`, `maxwidth`, `MAXWIDTH` or `MAX_WIDTH`
+pub(crate) fn rename_case(s: &str, case: &str) -> String {
+    let snake = to_snake_case(s);
+    match case {
+        "camelCase" => {
+            let camel = to_camel_case(&snake);
+            let mut chars = camel.chars();
+            chars
+                .next()
+                .map(|c| c.to_lowercase().chain(chars).collect())
+                .unwrap_or_default()
+        }
+        "PascalCase" => to_camel_case(&snake),
+        "lowercase" => snake.replace('_', ""),
+        "UPPERCASE" => snake.replace('_', "").to_uppercase(),
+        "SCREAMING_SNAKE_CASE" => snake.to_uppercase(),
+        _ => snake,
+    }
+}
+
+/// `min_width` -> `MinWidth`
+pub(crate) fn to_camel_case(s: &str) -> String {
+    s.split('_')
+        .filter(|x| !x.is_empty())
+        .map(|x| {
+            let mut chars = x.chars();
+            chars
+                .next()
+                .map(|c| c.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+#[derive(Clone, Copy)]
 pub(crate) enum Fns {
     Setter(Tys),
     Getter(Tys),
 }
 
+#[derive(Clone, Copy)]
 pub(crate) enum Tys {
     Basic,
     Ref,
@@ -169,6 +820,62 @@ pub(crate) enum Tys {
     VecInc,
     VecString,
     VecStringInc,
+    VecPair,
+    MapString,
+    Handle,
+    Array1,
+    Array2,
+    Vector,
+    DateTime,
+    OffsetDateTime,
+    Url,
+    OptionUrl,
+    Regex,
+    OptionRegex,
+    Cell,
+    WeakRc,
+    WeakArc,
+    ResultOk,
+    ResultErr,
+    Lazy,
+    TakeFrom,
+    Validate,
+    TryInto,
+    FromStr,
+    ArraySlice,
+    NonZero,
+    Range,
+    Into,
+    VecIter,
+    VecOwned,
+    Shared,
+    OptionBox,
+    OptionBoxed,
+    CowStr,
+    RefCell,
+    SharedLock,
+    PathBuf,
+    OptionPathBuf,
+    Bytes,
+    OsString,
+    OptionOsString,
+    CString,
+    Each,
+    Extend,
+    OptionVecInc,
+    OptionVecStringInc,
+    Push,
+    OptionOpt,
+    Without,
+    Reset,
+    Clear,
+    MapFn,
+    Modify,
+    Toggle,
+    Zeroize,
+    Lock,
+    Mutex,
+    RwLock,
     Option,
     OptionAsRef,
     OptionVec,
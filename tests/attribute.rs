@@ -0,0 +1,57 @@
+#[aksr::builder_attr]
+pub struct Session {
+    id: u64,
+    #[args(lazy)]
+    token: String,
+    #[args(lazy, alias = "expiry")]
+    expires_at: u64,
+}
+
+#[aksr::builder_attr(debug = false)]
+#[derive(Clone)]
+pub struct Handle {
+    name: String,
+}
+
+#[test]
+fn attribute() {
+    let session = Session::default().with_id(7);
+    assert_eq!(session.id(), 7);
+    assert_eq!(session.token(), None);
+    assert_eq!(session.expiry(), None);
+    assert!(format!("{:?}", session).starts_with("Session"));
+
+    let session = session.with_token("abc").with_expiry(60);
+    assert_eq!(session.token(), Some("abc"));
+    assert_eq!(session.expiry(), Some(60));
+
+    let handle = Handle::default().with_name("a").clone();
+    assert_eq!(handle.name(), "a");
+}
+
+#[aksr::builder_attr]
+pub struct Endpoint {
+    #[args(default = "localhost")]
+    host: String,
+    #[args(default = 8080)]
+    port: u16,
+    #[args(default = vec![1, 2])]
+    versions: Vec<u8>,
+    path: Option<String>,
+}
+
+#[aksr::builder_attr]
+pub struct Ratio(#[args(default = 1.0)] f32, #[args(default = 2.0)] f32);
+
+#[test]
+fn attribute_default() {
+    let endpoint = Endpoint::new();
+    assert_eq!(endpoint.host(), "localhost");
+    assert_eq!(endpoint.port(), 8080);
+    assert_eq!(endpoint.versions(), &[1, 2]);
+    assert_eq!(endpoint.path(), None);
+    assert_eq!(Endpoint::default().with_port(80).port(), 80);
+
+    let ratio = Ratio::new();
+    assert_eq!((ratio.nth_0(), ratio.nth_1()), (1.0, 2.0));
+}
@@ -0,0 +1,112 @@
+use aksr::Builder;
+
+#[derive(Builder, Debug, Clone, PartialEq)]
+pub enum Shape {
+    Empty,
+    Circle(f32),
+    Line(f32, f32),
+    Rect { w: f32, h: f32 },
+    Label { text: String },
+}
+
+#[test]
+fn variant_accessors() {
+    let shapes = [
+        Shape::Empty,
+        Shape::Circle(1.),
+        Shape::Line(0., 2.),
+        Shape::Rect { w: 3., h: 4. },
+        Shape::Label {
+            text: "a".to_string(),
+        },
+    ];
+    assert!(shapes[0].is_empty());
+    assert!(!shapes[1].is_empty());
+    assert_eq!(shapes[1].as_circle(), Some(&1.));
+    assert_eq!(shapes[2].as_line(), Some((&0., &2.)));
+    assert_eq!(shapes[3].as_rect(), Some((&3., &4.)));
+    assert_eq!(shapes[3].as_circle(), None);
+    assert_eq!(shapes[4].as_label(), Some(&"a".to_string()));
+    assert_eq!(shapes[4].clone().into_label(), Some("a".to_string()));
+    assert_eq!(shapes[3].clone().into_rect(), Some((3., 4.)));
+    assert_eq!(shapes[0].clone().into_circle(), None);
+}
+
+#[derive(Builder, Debug, PartialEq)]
+pub enum Widget {
+    Button { label: String, width: f32 },
+    Slider { width: f32, range: Vec<i32> },
+    Spacer,
+}
+
+#[test]
+fn variant_builders() {
+    let button: Widget = Widget::button().with_label("ok").with_width(3.0).into();
+    assert_eq!(
+        button,
+        Widget::Button {
+            label: "ok".to_string(),
+            width: 3.0
+        }
+    );
+    let slider = Widget::slider().with_width(1.0).with_range(&[0, 10]);
+    assert_eq!(slider.width(), 1.0);
+    let slider = Widget::from(slider);
+    assert_eq!(slider.as_slider(), Some((&1.0, &vec![0, 10])));
+}
+
+#[derive(Builder, Debug, PartialEq)]
+pub enum Node<'a, T> {
+    Leaf {
+        #[args(default = 1)]
+        weight: u32,
+        value: Option<T>,
+    },
+    Link {
+        name: &'a str,
+    },
+}
+
+#[test]
+fn variant_builders_generic() {
+    let leaf: Node<'_, u8> = Node::leaf().with_value(7).into();
+    assert_eq!(
+        leaf,
+        Node::Leaf {
+            weight: 1,
+            value: Some(7)
+        }
+    );
+    let link: Node<'_, u8> = Node::link().with_name("next").into();
+    assert_eq!(link.as_link(), Some(&"next"));
+}
+
+#[derive(Builder, serde::Serialize, Debug, PartialEq)]
+pub enum Event {
+    Click {
+        /// horizontal position
+        #[serde(rename = "px")]
+        x: i32,
+        #[cfg(not(any()))]
+        #[serde(skip)]
+        y: i32,
+    },
+}
+
+#[test]
+fn variant_builders_attrs() {
+    let click: Event = Event::click().with_x(1).with_y(2).into();
+    assert_eq!(click, Event::Click { x: 1, y: 2 });
+}
+
+#[derive(Builder, Debug, PartialEq)]
+#[args(docs = false)]
+pub enum Command {
+    Run { program: String },
+}
+
+#[test]
+fn variant_builders_docs() {
+    let run: Command = Command::run().with_program("ls").into();
+    assert_eq!(run.as_run(), Some(&"ls".to_string()));
+}
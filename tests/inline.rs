@@ -0,0 +1,37 @@
+aksr::builder! {
+    #[derive(Debug, Clone)]
+    #[args(is_default)]
+    pub struct Config<'a> {
+        depth: usize = 3,
+        #[args(alias = "label")]
+        name: String = "root".into(),
+        #[args(inc = true)]
+        tags: Vec<&'a str>,
+        ratio: Option<f32> = Some(0.5),
+    }
+}
+
+#[test]
+fn inline() {
+    let config = Config::default();
+    assert_eq!(config.depth(), 3);
+    assert_eq!(config.label(), "root");
+    assert!(config.tags().is_empty());
+    assert_eq!(config.ratio(), Some(0.5));
+
+    let config = config.with_depth(5).with_tags(&["a"]).with_tags_inc(&["b"]);
+    assert_eq!(config.depth(), 5);
+    assert_eq!(config.tags(), &["a", "b"]);
+    assert!(!config.is_default());
+}
+
+#[test]
+fn inline_is_default() {
+    assert!(Config::default().is_default());
+    assert!(!Config::default().with_depth(0).is_default());
+    assert!(!Config::default().with_label("").is_default());
+    assert!(Config::default()
+        .with_depth(3)
+        .with_label("root")
+        .is_default());
+}
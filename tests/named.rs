@@ -218,7 +218,7 @@ fn all() {
         .with_rc_string(Rc::new("Rc_String".to_string()))
         .with_weak_rc_string(Weak::new())
         .with_arc_string(Arc::new("Arc_String".to_string()))
-        .with_refcell_u8(RefCell::new(1))
+        .with_refcell_u8(1)
         .with_arc_mutex_u8(Arc::new(Mutex::new(1)))
         .with_arc_rwlock_string(Arc::new(RwLock::new("RwLock_String".to_string())))
         .with_cow_str(Cow::Borrowed("borrowed_cow"))
@@ -227,7 +227,7 @@ fn all() {
         .with_opt_u8(1)
         .with_opt_tuple((1, -1))
         .with_opt_array([1])
-        .with_opt_box_u8(Box::new(1))
+        .with_opt_box_u8(1)
         .with_opt_str("optional_str")
         .with_opt_vec_str(&["opt_str1", "opt_str2"])
         .with_opt_string("optional_string")
@@ -353,7 +353,7 @@ fn all() {
     assert_eq!(entity.opt_array, Some([1]));
     assert_eq!(entity.opt_array(), Some(&[1]));
     assert_eq!(entity.opt_box_u8, Some(Box::new(1)));
-    assert_eq!(entity.opt_box_u8(), Some(&Box::new(1)));
+    assert_eq!(entity.opt_box_u8(), Some(&1));
     assert_eq!(entity.opt_str, Some("optional_str"));
     assert_eq!(entity.opt_str(), Some("optional_str"));
     assert_eq!(entity.opt_string, Some("optional_string".to_string()));
@@ -375,3 +375,2370 @@ fn all() {
     assert_eq!(entity.result, Ok(1));
     assert_eq!(entity.result(), &Ok(1));
 }
+
+#[test]
+fn strs() {
+    let entity: Entity<'_, u8, String> = Entity::default().with_vec_string(&["str1", "str2"]);
+    assert_eq!(
+        entity.vec_string_strs().collect::<Vec<_>>(),
+        ["str1", "str2"]
+    );
+}
+
+#[derive(Builder, Default)]
+pub struct Meter {
+    #[args(handle)]
+    count: Arc<Mutex<u8>>,
+    #[args(handle)]
+    name: Arc<RwLock<String>>,
+}
+
+#[test]
+fn handle() {
+    let meter = Meter::default().with_count(Arc::new(Mutex::new(1)));
+    let count = meter.count_handle();
+    *count.lock().unwrap() += 1;
+    assert_eq!(*meter.count().lock().unwrap(), 2);
+    assert_eq!(Arc::strong_count(&count), 2);
+    meter.name_handle().write().unwrap().push_str("meter");
+    assert_eq!(&*meter.name().read().unwrap(), "meter");
+}
+
+#[derive(Builder, Default)]
+#[args(is_default)]
+pub struct Options {
+    threshold: f32,
+    name: String,
+    tags: Vec<String>,
+    x: X,
+}
+
+#[test]
+fn is_default() {
+    let options = Options::default();
+    assert!(options.is_default());
+    let options = options.with_threshold(0.5);
+    assert!(!options.is_default());
+    let options = options.with_threshold(0.).with_tags(&["a"]);
+    assert!(!options.is_default());
+    let options = options.with_tags(&[]).with_x(X { a: 1, b: 0. });
+    assert!(!options.is_default());
+    assert!(options.with_x(X::default()).with_name("").is_default());
+}
+
+#[derive(Builder)]
+#[args(is_default)]
+pub struct Checkout {
+    #[args(default = "main")]
+    branch: String,
+    #[args(default = 8)]
+    jobs: u8,
+    depth: Option<u32>,
+}
+
+#[test]
+fn is_default_declared() {
+    let checkout = Checkout::default();
+    assert!(checkout.is_default());
+    let checkout = checkout.with_jobs(0);
+    assert!(!checkout.is_default());
+    let checkout = checkout.with_jobs(8).with_branch("dev");
+    assert!(!checkout.is_default());
+    assert!(checkout.with_branch("main").is_default());
+}
+
+#[derive(Builder, Default)]
+pub struct Layer {
+    width: u32,
+    name: Option<String>,
+    tags: Vec<String>,
+    #[args(track_set)]
+    set: u8,
+}
+
+#[test]
+fn track_set() {
+    let layer = Layer::default();
+    assert!(layer.set_fields().is_empty());
+    let layer = layer.with_width(0).with_tags(&["a"]);
+    assert!(layer.is_set("width"));
+    assert!(layer.is_set("tags"));
+    assert!(!layer.is_set("name"));
+    assert!(!layer.is_set("set"));
+    assert!(!layer.is_set("unknown"));
+    assert_eq!(layer.set_fields(), vec!["width", "tags"]);
+}
+
+#[derive(Builder, Default)]
+#[args(is_default)]
+pub struct Gutter {
+    top: u32,
+    bottom: u32,
+    #[args(track_set)]
+    set: u8,
+}
+
+#[test]
+fn track_set_is_default() {
+    let gutter = Gutter::default().with_top(0);
+    assert!(gutter.is_set("top"));
+    assert!(gutter.is_default());
+    assert!(!gutter.with_bottom(1).is_default());
+}
+
+#[derive(Builder, Debug, Default)]
+#[args(view)]
+pub struct Snapshot<'a, A: std::fmt::Debug, B> {
+    f32: f32,
+    string: String,
+    vec_string: Vec<String>,
+    a: A,
+    b: B,
+    opt_u8: Option<u8>,
+    opt_tuple: Option<(u8, i8)>,
+    opt_string: Option<String>,
+    opt_vec_str: Option<Vec<&'a str>>,
+}
+
+#[test]
+fn view() {
+    let entity: Snapshot<'_, u8, String> = Snapshot::default()
+        .with_f32(32.)
+        .with_string("string")
+        .with_vec_string(&["str1", "str2"])
+        .with_opt_u8(1)
+        .with_opt_string("optional_string")
+        .with_opt_vec_str(&["opt_str1"])
+        .with_opt_tuple((1, -1))
+        .with_a(89);
+    let view = entity.view();
+    let copied = view;
+    assert_eq!(view.f32, 32.);
+    assert_eq!(view.string, "string");
+    assert_eq!(view.vec_string, &["str1", "str2"]);
+    assert_eq!(view.opt_u8, Some(1));
+    assert_eq!(view.opt_string, Some("optional_string"));
+    assert_eq!(view.opt_vec_str, Some(&["opt_str1"][..]));
+    assert_eq!(view.opt_tuple, Some(&(1, -1)));
+    assert_eq!(copied.a, &89);
+    assert_eq!(copied.b, "");
+}
+
+#[derive(Builder, Default)]
+pub struct Request<'a> {
+    metadata: HashMap<String, String>,
+    headers: Vec<(String, String)>,
+    params: Vec<(&'a str, usize)>,
+}
+
+#[test]
+fn vec_pair() {
+    let request = Request::default()
+        .with_headers(&[("accept".to_string(), "*/*".to_string())])
+        .with_headers_insert("host".to_string(), "localhost".to_string())
+        .with_params_insert("page", 2);
+    assert_eq!(request.headers().len(), 2);
+    assert_eq!(
+        request.headers_get("host").map(String::as_str),
+        Some("localhost")
+    );
+    assert_eq!(
+        request.headers_get("accept").map(String::as_str),
+        Some("*/*")
+    );
+    assert_eq!(request.headers_get("missing"), None);
+    assert_eq!(request.params_get("page"), Some(&2));
+    assert_eq!(request.params(), &[("page", 2)]);
+
+    let request = request.with_metadata(HashMap::from([("k".to_string(), "v".to_string())]));
+    assert_eq!(request.metadata_entries().collect::<Vec<_>>(), [("k", "v")]);
+}
+
+#[derive(Builder)]
+pub struct Job {
+    #[args(ok_wrap)]
+    status: Result<u8, String>,
+    raw: Result<u8, String>,
+}
+
+impl Default for Job {
+    fn default() -> Self {
+        Self {
+            status: Ok(0),
+            raw: Ok(0),
+        }
+    }
+}
+
+#[test]
+fn ok_wrap() {
+    let job = Job::default().with_status(1).with_raw(Err("e".into()));
+    assert_eq!(job.status(), &Ok(1));
+    assert_eq!(job.raw(), &Err("e".to_string()));
+    let job = job.with_status_err("failed".to_string());
+    assert_eq!(job.status(), &Err("failed".to_string()));
+}
+
+#[derive(Builder, Default)]
+pub struct Observer {
+    local: std::rc::Weak<String>,
+    shared: std::sync::Weak<String>,
+}
+
+#[test]
+fn weak_from() {
+    let local = Rc::new("local".to_string());
+    let shared = Arc::new("shared".to_string());
+    let observer = Observer::default()
+        .with_local_from(&local)
+        .with_shared_from(&shared);
+    assert_eq!(observer.local().upgrade(), Some(local));
+    assert_eq!(observer.shared().upgrade(), Some(shared));
+    let observer = observer.with_local(std::rc::Weak::new());
+    assert!(observer.local().upgrade().is_none());
+}
+
+#[derive(Builder)]
+pub struct Registry<'a> {
+    names: std::sync::LazyLock<Vec<&'a str>>,
+    size: std::cell::LazyCell<usize>,
+}
+
+#[test]
+fn lazy() {
+    let registry = Registry {
+        names: std::sync::LazyLock::new(|| vec!["a", "b"]),
+        size: std::cell::LazyCell::new(|| 2),
+    };
+    assert_eq!(registry.names(), &vec!["a", "b"]);
+    assert_eq!(registry.size(), &2);
+}
+
+/// A user type named like `once_cell::sync::Lazy`
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Lazy<T>(T);
+
+#[derive(Builder, Default)]
+pub struct Deferred {
+    value: Lazy<u8>,
+}
+
+#[test]
+fn lazy_shadowed() {
+    let deferred = Deferred::default().with_value(Lazy(3));
+    assert_eq!(deferred.value(), &Lazy(3));
+}
+
+#[derive(Builder, Default)]
+pub struct Buffer {
+    #[args(take_from)]
+    data: Vec<u8>,
+    #[args(take_from, alias = "label")]
+    name: String,
+}
+
+#[test]
+fn take_from() {
+    let mut a = Buffer::default();
+    let mut b = Buffer::default().with_data([1, 2, 3]).with_label("b");
+    a.take_data_from(&mut b).take_label_from(&mut b);
+    assert_eq!(a.data(), &[1, 2, 3]);
+    assert_eq!(a.label(), "b");
+    assert!(b.data().is_empty());
+    assert!(b.label().is_empty());
+}
+
+#[derive(Builder, Default)]
+pub struct Credentials {
+    user: String,
+    #[args(secret)]
+    password: String,
+    #[args(secret, expose)]
+    token: Option<String>,
+}
+
+#[test]
+fn secret() {
+    let credentials = Credentials::default()
+        .with_user("user")
+        .with_password("hunter2")
+        .with_token("abc");
+    assert_eq!(credentials.user(), "user");
+    assert_eq!(credentials.password_redacted(), "***");
+    assert_eq!(credentials.token_redacted(), "***");
+    assert_eq!(credentials.token_exposed(), Some("abc"));
+    assert_eq!(credentials.password, "hunter2");
+}
+
+// `secret` only redacts, wiping needs `zeroize` as well
+#[cfg(feature = "zeroize")]
+#[derive(Builder, Default)]
+pub struct Vault {
+    #[args(secret, zeroize, take_from)]
+    key: String,
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn secret_zeroize() {
+    let mut vault = Vault::default().with_key("k1");
+    assert_eq!(vault.key_redacted(), "***");
+    vault.zeroize_key();
+    assert!(vault.key.is_empty());
+    let mut other = Vault::default().with_key("k2");
+    vault.take_key_from(&mut other);
+    assert_eq!((vault.key.as_str(), other.key.as_str()), ("k2", ""));
+}
+
+#[derive(Builder, Default)]
+#[args(ffi)]
+#[repr(C)]
+pub struct PointF32 {
+    x: f32,
+    y: f32,
+    label: String,
+    tag: char,
+}
+
+/// `char` fields get no shim, the name is free
+pub fn point_f32_tag_get(point: &PointF32) -> char {
+    point.tag
+}
+
+#[test]
+fn ffi() {
+    let point = PointF32::default().with_x(1.).with_y(2.).with_label("p");
+    let ptr: *const PointF32 = &point;
+    assert_eq!(unsafe { point_f32_x_get(ptr) }, 1.);
+    assert_eq!(unsafe { point_f32_y_get(ptr) }, 2.);
+    assert_eq!(point.label(), "p");
+    assert_eq!(point_f32_tag_get(&point.with_tag('t')), 't');
+}
+
+#[derive(Builder, Default)]
+pub struct Blend {
+    #[args(clamp = "0.0..=1.0")]
+    alpha: f32,
+    #[args(clamp = "1..")]
+    layers: u8,
+    #[args(clamp = "..=100")]
+    quality: Option<i32>,
+}
+
+#[test]
+fn clamp() {
+    let blend = Blend::default()
+        .with_alpha(1.5)
+        .with_layers(0)
+        .with_quality(200);
+    assert_eq!(blend.alpha(), 1.);
+    assert_eq!(blend.layers(), 1);
+    assert_eq!(blend.quality(), Some(100));
+    assert_eq!(blend.with_alpha(-1.).alpha(), 0.);
+}
+
+#[derive(Builder, Default)]
+pub struct Palette {
+    #[args(renamed_from = "with_colour")]
+    color: String,
+    #[args(renamed_from = "size")]
+    width: u32,
+}
+
+#[test]
+#[allow(deprecated)]
+fn renamed_from() {
+    let palette = Palette::default().with_colour("red").with_width(3);
+    assert_eq!(palette.color(), "red");
+    assert_eq!(palette.size(), 3);
+}
+
+#[derive(Builder, Default)]
+pub struct Pipeline {
+    #[args(debug_log)]
+    stages: Vec<String>,
+    #[args(debug_log, clamp = "0.0..=1.0")]
+    ratio: f32,
+}
+
+#[test]
+fn debug_log() {
+    let pipeline = Pipeline::default().with_stages(&["decode"]).with_ratio(2.);
+    assert_eq!(pipeline.stages(), &["decode"]);
+    assert_eq!(pipeline.ratio(), 1.);
+}
+
+#[derive(Builder, Default, Debug)]
+#[args(invariant = "Self::check")]
+pub struct Bounds {
+    min: i32,
+    max: i32,
+}
+
+impl Bounds {
+    fn check(&self) {
+        assert!(self.min <= self.max, "min > max: {:?}", self);
+    }
+}
+
+#[test]
+fn invariant() {
+    let bounds = Bounds::default().with_max(10).with_min(5);
+    assert_eq!((bounds.min(), bounds.max()), (5, 10));
+    assert!(std::panic::catch_unwind(|| Bounds::default().with_min(1)).is_err());
+}
+
+#[derive(Builder, Default)]
+pub struct Stats {
+    samples: Vec<f32>,
+    #[args(readonly)]
+    mean: f32,
+}
+
+impl Stats {
+    pub fn with_samples_mean(mut self, samples: &[f32]) -> Self {
+        self.mean = samples.iter().sum::<f32>() / samples.len() as f32;
+        self.with_samples(samples)
+    }
+}
+
+#[test]
+fn readonly() {
+    let stats = Stats::default().with_samples_mean(&[1., 2., 3.]);
+    assert_eq!(stats.samples(), &[1., 2., 3.]);
+    assert_eq!(stats.mean(), 2.);
+}
+
+#[derive(Builder, Default)]
+#[args(view)]
+pub struct Login {
+    user: String,
+    #[args(writeonly)]
+    password: String,
+}
+
+#[test]
+fn writeonly() {
+    let login = Login::default().with_user("admin").with_password("hunter2");
+    assert_eq!(login.user(), "admin");
+    assert_eq!(login.password, "hunter2");
+    assert_eq!(login.view().user, "admin");
+}
+
+#[derive(Builder, Default)]
+pub struct Extent {
+    width: f32,
+    height: f32,
+    unit: String,
+}
+
+#[derive(Builder, Default)]
+#[args(nested = "extent.width: f32", nested = "extent.unit: String")]
+pub struct Window {
+    title: String,
+    extent: Extent,
+}
+
+#[test]
+fn nested() {
+    let window = Window::default().with_title("main").with_extent(
+        Extent::default()
+            .with_width(640.)
+            .with_height(480.)
+            .with_unit("px"),
+    );
+    assert_eq!(window.width(), 640.);
+    assert_eq!(window.unit(), "px");
+    assert_eq!(window.extent().height(), 480.);
+}
+
+#[derive(Builder, Default)]
+#[args(field_id)]
+pub struct Column<'a> {
+    name: &'a str,
+    #[args(alias = "max_width")]
+    width: usize,
+    values: Vec<f32>,
+}
+
+#[test]
+fn field_id() {
+    let column = Column::default()
+        .with_name("x")
+        .with_max_width(8)
+        .with_values(&[1.0]);
+    assert_eq!(ColumnField::ALL.len(), 3);
+    assert_eq!(ColumnField::MaxWidth.name(), "max_width");
+    for field in ColumnField::ALL {
+        match column.get(field) {
+            ColumnFieldValue::Name(x) => assert_eq!(*x, "x"),
+            ColumnFieldValue::MaxWidth(x) => assert_eq!(*x, 8),
+            ColumnFieldValue::Values(x) => assert_eq!(x, &[1.0]),
+        }
+    }
+}
+
+#[derive(Builder, Default)]
+pub struct Counter {
+    hits: std::cell::Cell<u32>,
+    last: std::cell::Cell<Option<char>>,
+}
+
+#[test]
+fn cell() {
+    let counter = Counter::default().with_hits(2).with_last(Some('a'));
+    counter.hits.set(counter.hits() + 1);
+    assert_eq!(counter.hits(), 3);
+    assert_eq!(counter.last(), Some('a'));
+}
+
+/// A user type named like `std::cell::Cell`, without type arguments
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Cell {
+    row: u32,
+}
+
+#[derive(Builder, Default)]
+pub struct Sheet {
+    cursor: Cell,
+    #[args(clamp = "..=9")]
+    zoom: std::cell::Cell<u8>,
+}
+
+#[test]
+fn cell_shadowed() {
+    let sheet = Sheet::default().with_cursor(Cell { row: 2 }).with_zoom(20);
+    assert_eq!(sheet.cursor(), &Cell { row: 2 });
+    assert_eq!(sheet.zoom(), 9);
+}
+
+pub mod frames {
+    /// A user type named like `std::cell::RefCell`, without type arguments
+    #[derive(Debug, Default, Clone, PartialEq)]
+    pub struct RefCell {
+        pub depth: u8,
+    }
+}
+
+#[derive(Builder, Default)]
+pub struct Stack {
+    top: frames::RefCell,
+}
+
+#[test]
+fn refcell_shadowed() {
+    let stack = Stack::default().with_top(frames::RefCell { depth: 4 });
+    assert_eq!(stack.top(), &frames::RefCell { depth: 4 });
+}
+
+#[derive(Builder, Default)]
+#[args(apply)]
+pub struct Server {
+    port: u16,
+    tls: bool,
+}
+
+#[test]
+fn apply() {
+    let common = |s: Server| s.with_port(443);
+    let prod = true;
+    let server = Server::default()
+        .apply(common)
+        .apply(|s| if prod { s.with_tls(true) } else { s });
+    assert_eq!(server.port(), 443);
+    assert!(server.tls());
+}
+
+#[derive(Builder, Default)]
+#[builder(setter_prefix = "set")]
+pub struct Margins {
+    top: u32,
+    #[args(setter_prefix = "with")]
+    bottom: u32,
+}
+
+#[test]
+fn struct_setter_prefix() {
+    let margins = Margins::default().set_top(1).with_bottom(2);
+    assert_eq!((margins.top(), margins.bottom()), (1, 2));
+}
+
+#[derive(Builder, Default)]
+#[builder(getter_prefix = "get")]
+pub struct Frame {
+    index: usize,
+    #[args(getter_prefix = "peek")]
+    data: Vec<u8>,
+}
+
+#[test]
+fn struct_getter_prefix() {
+    let frame = Frame::default().with_index(3).with_data([1, 2]);
+    assert_eq!(frame.get_index(), 3);
+    assert_eq!(frame.peek_data(), &[1, 2]);
+}
+
+mod visibility {
+    use aksr::Builder;
+
+    #[derive(Builder, Default)]
+    #[builder(visibility = "pub(crate)", getter_visibility = "pub")]
+    pub struct Limits {
+        retries: u8,
+    }
+
+    pub fn limits() -> Limits {
+        Limits::default().with_retries(3)
+    }
+
+    /// helpers follow `visibility` as well, private ones are left to the module
+    #[derive(Builder, Default)]
+    #[builder(visibility = "pub(self)", is_default, view, build)]
+    pub struct Quota {
+        #[args(required)]
+        used: u32,
+        #[args(track_set)]
+        set: u8,
+    }
+
+    pub fn quota_is_default(x: u32) -> bool {
+        let quota = Quota::new(0).apply(|q| q.with_used(x)).when(false, |q| q);
+        quota.is_set("used") && quota.view().used == x && quota.is_default()
+    }
+}
+
+#[test]
+fn visibility() {
+    assert_eq!(visibility::limits().retries(), 3);
+    assert_eq!(visibility::Limits::default().with_retries(1).retries(), 1);
+    assert!(visibility::quota_is_default(0));
+    assert!(!visibility::quota_is_default(1));
+}
+
+#[derive(Builder, Default)]
+#[builder(inline = "always")]
+pub struct Pixel {
+    value: u8,
+    #[args(inline = "never")]
+    label: String,
+}
+
+#[test]
+fn inline() {
+    let pixel = Pixel::default().with_value(255).with_label("white");
+    assert_eq!((pixel.value(), pixel.label()), (255, "white"));
+}
+
+#[derive(Builder, Default)]
+#[builder(inc)]
+pub struct Batch {
+    ids: Vec<u32>,
+    names: Vec<String>,
+    #[args(inc = false)]
+    weights: Vec<f32>,
+}
+
+#[test]
+fn struct_inc() {
+    let batch = Batch::default()
+        .with_ids(&[1])
+        .with_ids_inc(&[2])
+        .with_names(&["a"])
+        .with_names_inc(&["b"])
+        .with_weights(&[0.5]);
+    assert_eq!(batch.ids(), &[1, 2]);
+    assert_eq!(batch.names(), &["a", "b"]);
+    assert_eq!(batch.weights(), &[0.5]);
+}
+
+#[allow(non_snake_case)]
+#[derive(Builder, Default)]
+#[builder(rename_all = "snake_case")]
+pub struct ApiPayload {
+    userId: u64,
+    displayName: String,
+}
+
+#[test]
+fn rename_all() {
+    let payload = ApiPayload::default().with_user_id(1).with_display_name("a");
+    assert_eq!((payload.user_id(), payload.display_name()), (1, "a"));
+}
+
+#[derive(Builder, Default)]
+#[builder(docs = false)]
+pub struct Token {
+    #[args(secret, expose, alias = "value")]
+    raw: String,
+}
+
+#[test]
+fn docs() {
+    let token = Token::default().with_value("t");
+    assert_eq!(token.value_exposed(), "t");
+}
+
+#[derive(Builder, Default)]
+#[builder(docs = false, ffi)]
+#[repr(C)]
+pub struct Rgba {
+    r: u8,
+    a: f32,
+}
+
+#[test]
+fn docs_ffi() {
+    let rgba = Rgba::default().with_r(255).with_a(0.5);
+    assert_eq!(unsafe { rgba_r_get(&rgba) }, 255);
+    assert_eq!(unsafe { rgba_a_get(&rgba) }, 0.5);
+}
+
+#[derive(Builder, Default)]
+#[builder(mode = "getters")]
+pub struct Metrics {
+    count: usize,
+    #[args(setter = true)]
+    label: String,
+}
+
+#[derive(Builder, Default)]
+#[builder(mode = "setters")]
+pub struct Request2 {
+    retries: u8,
+}
+
+#[test]
+fn mode() {
+    let metrics = Metrics::default().with_label("m");
+    assert_eq!((metrics.count(), metrics.label()), (0, "m"));
+    let request = Request2::default().with_retries(2);
+    assert_eq!(request.retries, 2);
+}
+
+#[derive(Builder, Default)]
+#[builder(must_use)]
+pub struct Retry {
+    attempts: u8,
+    #[args(must_use = "all")]
+    backoff: f32,
+}
+
+#[test]
+fn must_use() {
+    let retry = Retry::default().with_attempts(3).with_backoff(1.5);
+    assert_eq!((retry.attempts(), retry.backoff()), (3, 1.5));
+}
+
+#[derive(Builder, Default)]
+#[builder(default_skip)]
+pub struct Engine {
+    #[args(allow(getter, setter))]
+    threads: usize,
+    #[args(allow(getter))]
+    cache_hits: u64,
+    scratch: Vec<u8>,
+}
+
+#[test]
+fn default_skip() {
+    let engine = Engine::default().with_threads(4);
+    assert_eq!((engine.threads(), engine.cache_hits()), (4, 0));
+    assert!(engine.scratch.is_empty());
+}
+
+mod public_only {
+    use aksr::Builder;
+
+    #[derive(Builder, Default)]
+    #[builder(public_only)]
+    pub struct Cache {
+        pub capacity: usize,
+        entries: Vec<u64>,
+        #[args(allow(getter))]
+        hits: u64,
+    }
+
+    impl Cache {
+        pub fn len(&self) -> usize {
+            self.entries.len()
+        }
+    }
+}
+
+#[test]
+fn public_only() {
+    let cache = public_only::Cache::default().with_capacity(16);
+    assert_eq!((cache.capacity(), cache.len(), cache.hits()), (16, 0, 0));
+}
+
+#[derive(aksr::Getters, aksr::Setters, Default)]
+#[args(view)]
+pub struct Halves {
+    a: u8,
+    b: String,
+}
+
+#[derive(aksr::Getters, Default)]
+pub struct ReadOnlyHalf {
+    a: u8,
+}
+
+#[test]
+fn getters_setters() {
+    let halves = Halves::default().with_a(1).with_b("b");
+    assert_eq!((halves.a(), halves.b()), (1, "b"));
+    assert_eq!(halves.view().a, 1);
+    assert_eq!(ReadOnlyHalf::default().a(), 0);
+}
+
+#[derive(aksr::Getters, aksr::Setters)]
+pub struct Acceptor {
+    #[args(required)]
+    port: u16,
+    #[args(default = 8)]
+    backlog: u32,
+    #[args(required)]
+    host: Option<String>,
+}
+
+#[test]
+fn getters_setters_construction() {
+    let acceptor = Acceptor::new(80).with_host("a").build().unwrap();
+    assert_eq!((acceptor.port(), acceptor.backlog()), (80, 8));
+    assert_eq!(acceptor.host(), Some("a"));
+}
+
+#[derive(Builder, Default)]
+#[builder(patch)]
+pub struct Profile {
+    name: String,
+    age: u8,
+    tags: Vec<String>,
+    nickname: Option<String>,
+}
+
+#[test]
+fn patch() {
+    let mut profile = Profile::default().with_name("a").with_age(30);
+    ProfilePatch::default()
+        .with_age(31)
+        .with_tags(&["x"])
+        .with_nickname(Some("b".to_string()))
+        .apply(&mut profile);
+    assert_eq!(profile.name(), "a");
+    assert_eq!(profile.age(), 31);
+    assert_eq!(profile.tags(), &["x"]);
+    assert_eq!(profile.nickname(), Some("b"));
+}
+
+#[cfg(feature = "serde")]
+#[derive(Builder, Default)]
+#[builder(merge_json)]
+pub struct Settings {
+    theme: String,
+    font_size: u8,
+    plugins: Vec<String>,
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn merge_json() {
+    let mut settings = Settings::default().with_theme("dark").with_font_size(12);
+    settings
+        .merge_json(r#"{ "font_size": 14, "plugins": ["git"] }"#)
+        .unwrap();
+    assert_eq!(settings.theme(), "dark");
+    assert_eq!(settings.font_size(), 14);
+    assert_eq!(settings.plugins(), &["git"]);
+    assert!(settings.merge_json(r#"{ "font_size": "big" }"#).is_err());
+    assert_eq!(settings.font_size(), 14);
+}
+
+#[cfg(feature = "tokio")]
+#[derive(Builder, Default)]
+pub struct Inbox {
+    unread: tokio::sync::Mutex<u32>,
+    subject: tokio::sync::RwLock<String>,
+}
+
+#[cfg(feature = "tokio")]
+#[test]
+fn tokio_locks() {
+    let inbox = Inbox::default()
+        .with_unread_inner(3)
+        .with_subject_inner("hi".to_string());
+    assert_eq!(inbox.unread_try_lock().map(|x| *x), Some(3));
+    assert_eq!(
+        inbox.subject_try_read().as_deref().map(String::as_str),
+        Some("hi")
+    );
+    let guard = inbox.unread().try_lock().unwrap();
+    assert!(inbox.unread_try_lock().is_none());
+    drop(guard);
+}
+
+#[cfg(feature = "ndarray")]
+#[derive(Builder, Default)]
+pub struct Tensor {
+    bias: ndarray::Array1<f32>,
+    weights: ndarray::Array2<f32>,
+}
+
+#[cfg(feature = "ndarray")]
+#[test]
+fn ndarray() {
+    let tensor = Tensor::default()
+        .with_bias(&[1., 2.])
+        .try_with_weights((2, 2), vec![1., 0., 0., 1.])
+        .unwrap();
+    assert_eq!(tensor.bias().len(), 2);
+    assert_eq!(tensor.weights()[[1, 1]], 1.);
+    assert!(Tensor::default()
+        .try_with_weights((2, 2), vec![1.])
+        .is_err());
+    assert_eq!(tensor.with_bias_vec(vec![3.]).bias()[0], 3.);
+}
+
+#[cfg(feature = "glam")]
+#[derive(Builder, Default)]
+pub struct Particle {
+    position: glam::Vec3,
+    tile: glam::UVec2,
+}
+
+#[cfg(feature = "glam")]
+#[test]
+fn glam() {
+    let particle = Particle::default()
+        .with_position_xyz(1., 2., 3.)
+        .with_tile_array([4, 5]);
+    assert_eq!(particle.position(), glam::Vec3::new(1., 2., 3.));
+    assert_eq!(particle.tile(), glam::UVec2::new(4, 5));
+    assert_eq!(particle.with_tile(glam::UVec2::ONE).tile().x, 1);
+}
+
+#[cfg(feature = "nalgebra")]
+#[derive(Builder, Default)]
+pub struct Body {
+    velocity: nalgebra::Vector3<f64>,
+    offset: nalgebra::Vector2<i32>,
+}
+
+#[cfg(feature = "nalgebra")]
+#[test]
+fn nalgebra() {
+    let body = Body::default()
+        .with_velocity_xyz(1., 0., -1.)
+        .with_offset_array([2, 3]);
+    assert_eq!(body.velocity(), nalgebra::Vector3::new(1., 0., -1.));
+    assert_eq!(body.offset(), nalgebra::Vector2::new(2, 3));
+}
+
+#[cfg(feature = "chrono")]
+#[derive(Builder, Default)]
+pub struct Commit {
+    authored: chrono::DateTime<chrono::Utc>,
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn chrono() {
+    let commit = Commit::default()
+        .try_with_authored("2024-01-02T03:04:05Z")
+        .unwrap();
+    assert_eq!(commit.authored().timestamp(), 1_704_164_645);
+    assert!(Commit::default().try_with_authored("yesterday").is_err());
+}
+
+#[cfg(feature = "time")]
+#[derive(Builder)]
+pub struct Lease {
+    expires: time::OffsetDateTime,
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn time() {
+    let lease = Lease {
+        expires: time::OffsetDateTime::UNIX_EPOCH,
+    };
+    let lease = lease.try_with_expires("2024-01-02T03:04:05Z").unwrap();
+    assert_eq!(lease.expires().unix_timestamp(), 1_704_164_645);
+    assert!(lease.try_with_expires("tomorrow").is_err());
+}
+
+#[cfg(feature = "url")]
+#[derive(Builder)]
+pub struct Remote {
+    origin: url::Url,
+    mirror: Option<url::Url>,
+}
+
+#[cfg(feature = "url")]
+#[test]
+fn url() {
+    let remote = Remote {
+        origin: url::Url::parse("http://localhost").unwrap(),
+        mirror: None,
+    };
+    let remote = remote
+        .try_with_origin("https://example.com/repo")
+        .unwrap()
+        .try_with_mirror("https://mirror.example.com")
+        .unwrap();
+    assert_eq!(remote.origin().host_str(), Some("example.com"));
+    assert_eq!(
+        remote.mirror().and_then(|x| x.host_str()),
+        Some("mirror.example.com")
+    );
+    assert!(remote.try_with_origin("not a url").is_err());
+}
+
+#[cfg(feature = "regex")]
+#[derive(Builder)]
+pub struct Filter {
+    include: regex::Regex,
+    exclude: Option<regex::Regex>,
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn regex() {
+    let filter = Filter {
+        include: regex::Regex::new(".*").unwrap(),
+        exclude: None,
+    };
+    let filter = filter
+        .try_with_include(r"\.rs$")
+        .unwrap()
+        .try_with_exclude("^target/")
+        .unwrap();
+    assert!(filter.include().is_match("src/lib.rs"));
+    assert!(filter.exclude().is_some_and(|x| x.is_match("target/a.rs")));
+    assert!(filter.try_with_include("(").is_err());
+}
+
+#[cfg(any(feature = "log", feature = "tracing"))]
+#[derive(Builder, Default)]
+pub struct Task {
+    #[args(trace)]
+    retries: u8,
+    #[args(trace, secret)]
+    token: String,
+}
+
+#[cfg(all(feature = "log", not(feature = "tracing")))]
+#[test]
+fn trace_log() {
+    static LINES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+    struct Lines;
+    impl log::Log for Lines {
+        fn enabled(&self, _: &log::Metadata) -> bool {
+            true
+        }
+        fn log(&self, record: &log::Record) {
+            LINES.lock().unwrap().push(record.args().to_string());
+        }
+        fn flush(&self) {}
+    }
+    log::set_logger(&Lines).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+    let _ = Task::default().with_retries(3).with_token("t");
+    assert_eq!(
+        *LINES.lock().unwrap(),
+        ["retries = 3", "token = <redacted>"]
+    );
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn trace_tracing() {
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+
+    #[derive(Default)]
+    struct Values(Arc<Mutex<Vec<String>>>);
+    impl Visit for &Values {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            if field.name() == "value" {
+                self.0.lock().unwrap().push(format!("{:?}", value));
+            }
+        }
+    }
+    impl tracing::Subscriber for Values {
+        fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, _: &Attributes<'_>) -> Id {
+            Id::from_u64(1)
+        }
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+        fn event(&self, event: &tracing::Event<'_>) {
+            event.record(&mut &*self);
+        }
+        fn enter(&self, _: &Id) {}
+        fn exit(&self, _: &Id) {}
+    }
+
+    let values = Values::default();
+    let lines = values.0.clone();
+    tracing::subscriber::with_default(values, || {
+        let _ = Task::default().with_retries(3).with_token("t");
+    });
+    assert_eq!(*lines.lock().unwrap(), ["3", "<redacted>"]);
+}
+
+#[derive(Builder, Default)]
+#[builder(ref_view)]
+pub struct Document {
+    title: String,
+    pages: Vec<u32>,
+    author: Option<String>,
+}
+
+fn page_count(doc: DocumentRef<'_>) -> usize {
+    doc.pages.len()
+}
+
+#[test]
+fn ref_view() {
+    let document = Document::default()
+        .with_title("t")
+        .with_pages(&[1, 2, 3])
+        .with_author("a");
+    let view = document.as_ref_view();
+    assert_eq!(view.title, "t");
+    assert_eq!(view.author, Some("a"));
+    assert_eq!(page_count(view), 3);
+}
+
+#[derive(Builder, Default)]
+#[builder(view_trait = "Sized2d")]
+pub struct Sprite {
+    w: f32,
+    h: f32,
+    name: String,
+}
+
+#[derive(Builder, Default)]
+#[builder(view_trait_impl = "Sized2d")]
+pub struct Tile {
+    w: f32,
+    h: f32,
+    name: String,
+}
+
+fn area(x: &impl Sized2d) -> f32 {
+    x.w() * x.h()
+}
+
+fn area_dyn(x: &dyn Sized2dDyn) -> f32 {
+    x.w() * x.h()
+}
+
+#[derive(Builder, Default)]
+#[builder(view_trait = "Sized2dDyn")]
+pub struct Rect2d {
+    w: f32,
+    h: f32,
+}
+
+#[test]
+fn view_trait() {
+    let sprite = Sprite::default().with_w(2.).with_h(3.).with_name("s");
+    assert_eq!(area(&sprite), 6.);
+    assert_eq!(Sized2d::name(&sprite), "s");
+    assert_eq!(area(&Tile::default().with_w(1.).with_h(4.)), 4.);
+    assert_eq!(area_dyn(&Rect2d::default().with_w(1.).with_h(5.)), 5.);
+}
+
+pub trait Configurable {
+    fn with_level(self, x: u8) -> Self;
+    fn level(&self) -> u8;
+    fn with_label(self, x: impl Into<String>) -> Self;
+    fn label(&self) -> &str;
+}
+
+#[derive(Builder, Default)]
+#[builder(impl_trait = "crate::Configurable")]
+pub struct Logger {
+    level: u8,
+    label: String,
+}
+
+fn configure<T: Configurable>(x: T) -> T {
+    x.with_level(3).with_label("app")
+}
+
+#[test]
+fn impl_trait() {
+    let logger = configure(Logger::default());
+    assert_eq!((logger.level(), logger.label()), (3, "app"));
+}
+
+#[derive(Builder, Debug)]
+pub struct Defaults {
+    #[args(default = "Vec::from([1, 2])")]
+    ids: Vec<u8>,
+    #[args(default = "guest")]
+    user: String,
+    #[args(default = 0.5)]
+    ratio: f32,
+    #[args(default)]
+    flags: Option<u8>,
+    retries: u8,
+}
+
+#[test]
+fn default_values() {
+    let defaults = Defaults::default();
+    assert_eq!(defaults.ids(), &[1, 2]);
+    assert_eq!(defaults.user(), "guest");
+    assert_eq!(defaults.ratio(), 0.5);
+    assert_eq!(defaults.flags(), None);
+    assert_eq!(defaults.retries(), 0);
+}
+
+#[derive(Builder, Debug)]
+pub struct TextDefaults {
+    #[args(default = "localhost")]
+    host: Option<String>,
+    #[args(default = "v1")]
+    version: Option<&'static str>,
+    #[args(default = "utf-8")]
+    charset: Box<str>,
+    #[args(default = "none")]
+    mode: Option<std::sync::Arc<str>>,
+}
+
+#[test]
+fn default_text() {
+    let defaults = TextDefaults::default();
+    assert_eq!(defaults.host(), Some("localhost"));
+    assert_eq!(defaults.version(), Some("v1"));
+    assert_eq!(&*defaults.charset, "utf-8");
+    assert_eq!(defaults.mode.as_deref(), Some("none"));
+}
+
+#[derive(Builder, Debug)]
+pub struct Connection {
+    #[args(required)]
+    host: String,
+    #[args(required)]
+    port: u16,
+    #[args(default = 30)]
+    timeout: u32,
+    verbose: bool,
+}
+
+#[test]
+fn required() {
+    let connection = Connection::new("db".to_string(), 5432).with_verbose(true);
+    assert_eq!((connection.host(), connection.port()), ("db", 5432));
+    assert_eq!(connection.timeout(), 30);
+    assert!(connection.verbose());
+}
+
+#[derive(Builder, Debug, Default)]
+pub struct Deploy {
+    #[args(required)]
+    image: Option<String>,
+    #[args(required, alias = "zone")]
+    region: Option<String>,
+    replicas: Option<u8>,
+}
+
+#[test]
+fn build() {
+    let deploy = Deploy::default()
+        .with_image("app:1")
+        .with_zone("eu")
+        .build();
+    assert_eq!(deploy.unwrap().image(), Some("app:1"));
+    let err = Deploy::default().with_replicas(2).build().unwrap_err();
+    assert_eq!(err, DeployBuildError::MissingField(vec!["image", "zone"]));
+    assert_eq!(
+        err.to_string(),
+        "missing required fields of `Deploy`: image, zone"
+    );
+}
+
+fn check_port(x: &u16) -> Result<(), String> {
+    match *x {
+        0 => Err("port must not be 0".to_string()),
+        _ => Ok(()),
+    }
+}
+
+fn check_name(x: &str) -> Result<(), String> {
+    match x.is_empty() {
+        true => Err("empty name".to_string()),
+        false => Ok(()),
+    }
+}
+
+#[derive(Builder, Default, Debug)]
+pub struct Listener {
+    #[args(validate = "check_port")]
+    port: u16,
+    #[args(validate = "check_name")]
+    name: String,
+}
+
+#[test]
+fn validate() {
+    let listener = Listener::default().with_port(80).with_name("http");
+    assert_eq!((listener.port(), listener.name()), (80, "http"));
+    assert_eq!(
+        Listener::default().try_with_port(0).err().as_deref(),
+        Some("port must not be 0")
+    );
+    assert_eq!(
+        Listener::default().try_with_port(8080).unwrap().port(),
+        8080
+    );
+    let err = std::panic::catch_unwind(|| Listener::default().with_name("")).unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<String>().map(|x| x.as_str()),
+        Some("invalid value for `name`: empty name")
+    );
+}
+
+#[derive(Builder, Default)]
+pub struct Socket {
+    #[args(try_into)]
+    port: u16,
+    #[args(try_into, clamp = "1..=64")]
+    backlog: u8,
+}
+
+#[test]
+fn try_into() {
+    let socket = Socket::default().try_with_port(8080_u32).unwrap();
+    assert_eq!(socket.port(), 8080);
+    assert!(Socket::default().try_with_port(70_000_i64).is_err());
+    let socket = socket.try_with_backlog(200_i32).unwrap();
+    assert_eq!(socket.backlog(), 64);
+}
+
+#[derive(Builder, Default)]
+pub struct Volume {
+    #[args(range = "0..=100")]
+    level: u8,
+    #[args(range = "0.0..1.0", range_mode = "panic")]
+    balance: f32,
+    #[args(range = "1..", range_mode = "try")]
+    channels: u32,
+}
+
+#[test]
+fn range() {
+    let volume = Volume::default().with_level(150).with_balance(0.5);
+    assert_eq!(volume.level(), 100);
+    assert_eq!(volume.balance(), 0.5);
+    assert!(std::panic::catch_unwind(|| Volume::default().with_balance(1.0)).is_err());
+    assert!(Volume::default().try_with_channels(0).is_err());
+    assert_eq!(
+        Volume::default().try_with_channels(2).unwrap().channels(),
+        2
+    );
+    assert!(std::panic::catch_unwind(|| Volume::default().with_channels(0)).is_err());
+}
+
+#[derive(Builder, Default, Debug)]
+pub struct Service {
+    #[args(required)]
+    image: Option<String>,
+    #[args(validate = "check_port")]
+    port: u16,
+    #[args(validate = "check_name")]
+    name: String,
+    #[args(range = "1..=8", range_mode = "try")]
+    replicas: u8,
+}
+
+#[test]
+fn build_report() {
+    let err = Service::default().with_name("web").build().unwrap_err();
+    assert_eq!(err, ServiceBuildError::MissingField(vec!["image"]));
+    let err = Service::default()
+        .with_image("web:1")
+        .with_name("web")
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ServiceBuildError::Invalid(vec![
+            ("port", "port must not be 0".to_string()),
+            ("replicas", "out of range 1..=8".to_string())
+        ])
+    );
+    assert_eq!(
+        err.to_string(),
+        "invalid fields of `Service`: `port`: port must not be 0; `replicas`: out of range 1..=8"
+    );
+    let service = Service::default()
+        .with_image("web:1")
+        .with_port(80)
+        .with_name("web")
+        .try_with_replicas(2)
+        .unwrap()
+        .build();
+    assert!(service.is_ok());
+}
+
+#[derive(Builder, Default, Debug)]
+#[args(build)]
+pub struct Quota {
+    #[args(range = "1..=8")]
+    limit: Option<u8>,
+}
+
+#[test]
+fn build_requested() {
+    assert!(Quota::default().build().is_ok());
+    assert!(Quota::default().with_limit(20).build().is_ok());
+    let err = Quota { limit: Some(20) }.build().unwrap_err();
+    assert_eq!(
+        err,
+        QuotaBuildError::Invalid(vec![("limit", "out of range 1..=8".to_string())])
+    );
+}
+
+/// `validate` alone does not generate `build()`
+#[derive(Builder, Default)]
+pub struct Probe {
+    #[args(validate = "check_port")]
+    port: u16,
+}
+
+impl Probe {
+    pub fn build(self) -> u16 {
+        self.port
+    }
+}
+
+#[test]
+fn build_not_requested() {
+    assert_eq!(Probe::default().with_port(80).build(), 80);
+}
+
+fn normalize(s: String) -> String {
+    s.trim().to_lowercase()
+}
+
+fn even(x: u32) -> u32 {
+    x & !1
+}
+
+#[derive(Builder, Default)]
+pub struct Account {
+    #[args(map = "normalize")]
+    email: String,
+    #[args(map = "normalize")]
+    nickname: Option<String>,
+    #[args(map = "even", range = "0..=10")]
+    slots: u32,
+}
+
+#[test]
+fn map() {
+    let account = Account::default()
+        .with_email("  Foo@Example.COM ")
+        .with_nickname(" Bob ")
+        .with_slots(7);
+    assert_eq!(account.email(), "foo@example.com");
+    assert_eq!(account.nickname(), Some("bob"));
+    assert_eq!(account.slots(), 6);
+    assert_eq!(Account::default().with_slots(13).slots(), 10);
+}
+
+#[derive(Builder, Default)]
+pub struct Rect {
+    #[args(on_set = "Self::recompute")]
+    width: u32,
+    #[args(on_set = "Self::recompute")]
+    height: u32,
+    #[args(setter = false)]
+    area: u32,
+}
+
+impl Rect {
+    fn recompute(&mut self) {
+        self.area = self.width * self.height;
+    }
+}
+
+#[test]
+fn on_set() {
+    let rect = Rect::default().with_width(3).with_height(4);
+    assert_eq!(rect.area(), 12);
+    assert_eq!(rect.with_width(5).area(), 20);
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Meters(f64);
+
+impl From<f64> for Meters {
+    fn from(x: f64) -> Self {
+        Self(x)
+    }
+}
+
+#[derive(Builder, Default)]
+pub struct Route {
+    #[args(into)]
+    name: String,
+    #[args(into)]
+    length: Meters,
+    #[args(into)]
+    lanes: u64,
+    #[args(into)]
+    note: Option<String>,
+}
+
+#[test]
+fn into() {
+    let route = Route::default()
+        .with_name(String::from("A1"))
+        .with_length(12.5)
+        .with_lanes(3_u8)
+        .with_note("toll".to_string());
+    assert_eq!(route.name(), "A1");
+    assert_eq!(route.length(), &Meters(12.5));
+    assert_eq!(route.lanes(), 3);
+    assert_eq!(route.note(), Some("toll"));
+    assert_eq!(Route::default().with_name("B2").name(), "B2");
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lexeme(u32);
+
+impl From<u32> for Lexeme {
+    fn from(x: u32) -> Self {
+        Self(x)
+    }
+}
+
+#[derive(Builder, Default)]
+pub struct Lexer {
+    lexemes: Vec<Lexeme>,
+    keywords: Vec<String>,
+}
+
+#[test]
+fn vec_iter() {
+    let lexer = Lexer::default()
+        .with_lexemes_iter((1..=3).map(Lexeme))
+        .with_keywords_iter(["fn", "let"]);
+    assert_eq!(lexer.lexemes(), &[Lexeme(1), Lexeme(2), Lexeme(3)]);
+    assert_eq!(lexer.keywords(), &["fn", "let"]);
+    let lexer = lexer
+        .with_lexemes_iter([7_u32])
+        .with_keywords_iter(vec![String::from("mut")]);
+    assert_eq!(lexer.lexemes(), &[Lexeme(7)]);
+    assert_eq!(lexer.keywords(), &["mut"]);
+}
+
+#[derive(Builder, Default)]
+pub struct Post {
+    #[args(each = "tag", into)]
+    tags: Vec<String>,
+    #[args(each = "score", inc = true)]
+    scores: Vec<u32>,
+}
+
+#[test]
+fn each() {
+    let mut post = Post::default().with_tags_iter(["rust"]).with_score(3);
+    for tag in ["macro", "derive"] {
+        post = post.with_tag(tag);
+    }
+    assert_eq!(post.tags(), &["rust", "macro", "derive"]);
+    assert_eq!(post.with_score(5).scores(), &[3, 5]);
+}
+
+#[derive(Builder, Default)]
+#[args(inc = true)]
+pub struct Samples {
+    values: Vec<i8>,
+    labels: Vec<String>,
+}
+
+#[test]
+fn inc_iter() {
+    let samples = Samples::default()
+        .with_values(&[1])
+        .with_values_inc(&[2])
+        .with_values_inc_iter((3..6).filter(|x| x % 2 == 1))
+        .with_labels(&["a"])
+        .with_labels_inc_iter(["b", "c"].into_iter().map(|x| x.to_uppercase()));
+    assert_eq!(samples.values(), &[1, 2, 3, 5]);
+    assert_eq!(samples.labels(), &["a", "B", "C"]);
+}
+
+#[derive(Builder, Default)]
+#[args(inc = true)]
+pub struct Index {
+    ids: std::collections::HashSet<u32>,
+    names: std::collections::BTreeMap<String, u32>,
+}
+
+#[test]
+fn inc_maps_and_sets() {
+    let index = Index::default()
+        .with_ids_inc([1, 2])
+        .with_ids_inc(vec![2, 3])
+        .with_names_inc([("a".to_string(), 1)])
+        .with_names_inc([("b".to_string(), 2)]);
+    assert_eq!(index.ids().len(), 3);
+    assert_eq!(index.names().get("b"), Some(&2));
+    assert_eq!(index.names().len(), 2);
+}
+
+#[derive(Builder, Default)]
+pub struct Query {
+    #[args(inc = true)]
+    filters: Option<Vec<u8>>,
+    #[args(inc = true)]
+    columns: Option<Vec<String>>,
+}
+
+#[test]
+fn inc_option_vec() {
+    let query = Query::default()
+        .with_filters_inc(&[1, 2])
+        .with_filters_inc_iter([3])
+        .with_columns_inc(&["id"])
+        .with_columns_inc_iter([String::from("name")]);
+    assert_eq!(query.filters(), Some(&[1, 2, 3][..]));
+    assert_eq!(query.columns().map(|x| x.len()), Some(2));
+    assert_eq!(Query::default().filters(), None);
+}
+
+#[derive(Builder, Default)]
+#[args(inc = true)]
+pub struct Scheduler {
+    queue: std::collections::VecDeque<u32>,
+    priorities: std::collections::BinaryHeap<u32>,
+}
+
+#[test]
+fn inc_deque_and_heap() {
+    let scheduler = Scheduler::default()
+        .with_queue_push_back(2)
+        .with_queue_push_front(1)
+        .with_queue_inc([3, 4])
+        .with_priorities_push(5)
+        .with_priorities_inc([9, 1]);
+    assert_eq!(
+        scheduler.queue().iter().copied().collect::<Vec<_>>(),
+        [1, 2, 3, 4]
+    );
+    assert_eq!(scheduler.priorities().peek(), Some(&9));
+    assert_eq!(scheduler.priorities().len(), 3);
+}
+
+#[derive(Builder, Default)]
+pub struct Proxy {
+    #[args(clamp = "1..=65535")]
+    port: Option<u32>,
+    host: Option<String>,
+}
+
+#[test]
+fn option_opt() {
+    let port = std::env::var("AKSR_PROXY_PORT")
+        .ok()
+        .and_then(|x| x.parse().ok());
+    let proxy = Proxy::default()
+        .with_port(8080)
+        .with_port_opt(port)
+        .with_host("localhost")
+        .with_host_opt(None);
+    assert_eq!(proxy.port(), None);
+    assert_eq!(proxy.host(), None);
+    assert_eq!(Proxy::default().with_port_opt(Some(0)).port(), Some(1));
+}
+
+#[derive(Builder, Default, Clone)]
+pub struct Template {
+    title: Option<String>,
+    margin: Option<f32>,
+}
+
+#[test]
+fn without() {
+    let template = Template::default().with_title("report").with_margin(1.5);
+    let plain = template.clone().without_margin();
+    assert_eq!(plain.title(), Some("report"));
+    assert_eq!(plain.margin(), None);
+    assert_eq!(template.without_title().title(), None);
+}
+
+#[derive(Builder)]
+pub struct Runner {
+    #[args(reset, default = 4)]
+    workers: usize,
+    #[args(reset)]
+    log: Vec<String>,
+}
+
+#[test]
+fn reset() {
+    let runner = Runner::default().with_workers(16).with_log(&["started"]);
+    assert_eq!(runner.workers(), 16);
+    let runner = runner.reset_workers().reset_log();
+    assert_eq!(runner.workers(), 4);
+    assert!(runner.log().is_empty());
+}
+
+#[derive(Builder, Default)]
+pub struct Cache {
+    entries: std::collections::HashMap<String, u32>,
+    order: Vec<String>,
+}
+
+#[test]
+fn clear() {
+    let mut cache = Cache::default()
+        .with_entries([("a".to_string(), 1)].into())
+        .with_order(&["a"]);
+    cache.clear_entries().clear_order();
+    assert!(cache.entries().is_empty());
+    assert!(cache.order().is_empty());
+}
+
+#[derive(Builder, Default)]
+pub struct Fetch {
+    url: String,
+    timeout: Option<u64>,
+    retries: u8,
+}
+
+#[test]
+fn with_if() {
+    let slow = true;
+    let request = Fetch::default()
+        .with_url("https://example.com")
+        .with_timeout_if(slow, 30)
+        .with_retries_if(!slow, 3);
+    assert_eq!(request.url(), "https://example.com");
+    assert_eq!(request.timeout(), Some(30));
+    assert_eq!(request.retries(), 0);
+}
+
+#[derive(Builder, Default)]
+pub struct Canvas {
+    #[args(map_with)]
+    width: f32,
+    #[args(map_with, clamp = "0..=255")]
+    alpha: u32,
+    #[args(map_with)]
+    layers: Vec<String>,
+}
+
+#[test]
+fn map_fn() {
+    let canvas = Canvas::default()
+        .with_width(100.0)
+        .map_width(|w| w * 2.0)
+        .with_alpha(200)
+        .map_alpha(|a| a + 100)
+        .with_layers(&["base"])
+        .map_layers(|mut x| {
+            x.push("top".into());
+            x
+        });
+    assert_eq!(canvas.width(), 200.0);
+    assert_eq!(canvas.alpha(), 255);
+    assert_eq!(canvas.layers(), &["base", "top"]);
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Margin {
+    top: f32,
+    bottom: f32,
+}
+
+#[derive(Builder, Default)]
+pub struct Page {
+    margin: Margin,
+    lines: Vec<String>,
+    number: u32,
+}
+
+#[test]
+fn modify() {
+    let mut page = Page::default().with_number(1);
+    page.modify_margin(|m| m.top = 2.0)
+        .modify_lines(|x| x.push("hello".into()));
+    assert_eq!(
+        page.margin(),
+        &Margin {
+            top: 2.0,
+            bottom: 0.0
+        }
+    );
+    assert_eq!(page.lines(), &["hello"]);
+    assert_eq!(page.number(), 1);
+}
+
+fn positive(x: &i32) -> Result<(), String> {
+    match *x > 0 {
+        true => Ok(()),
+        false => Err("must be positive".into()),
+    }
+}
+
+#[derive(Builder, Default)]
+#[builder(setters = "mut")]
+pub struct Actor {
+    name: String,
+    frames: Vec<u32>,
+    #[args(validate = "positive", on_set = "Self::touch")]
+    speed: i32,
+    hidden: Option<bool>,
+    #[args(setter_prefix = "with", setters = "owned")]
+    layer: u8,
+    #[args(setter = false)]
+    touched: usize,
+}
+
+impl Actor {
+    fn touch(&mut self) {
+        self.touched += 1;
+    }
+}
+
+#[test]
+fn setters_mut() {
+    let mut actors = [Actor::default().with_layer(1)];
+    for actor in actors.iter_mut() {
+        actor
+            .set_name("hero")
+            .set_frames_iter([1_u32, 2])
+            .set_speed(3)
+            .set_hidden_if(true, false);
+        actor.try_set_speed(4).unwrap();
+        assert!(actor.try_set_speed(-1).is_err());
+        actor.without_hidden();
+    }
+    let actor = &actors[0];
+    assert_eq!(actor.name(), "hero");
+    assert_eq!(actor.frames(), &[1, 2]);
+    assert_eq!((actor.speed(), actor.touched()), (4, 2));
+    assert_eq!((actor.hidden(), actor.layer()), (None, 1));
+}
+
+#[derive(Builder, Default)]
+#[builder(setters = "both")]
+pub struct Player {
+    name: String,
+    #[args(inc = true)]
+    items: Vec<u32>,
+    score: Option<u64>,
+}
+
+#[test]
+fn setters_both() {
+    let mut player = Player::default()
+        .with_name("ann")
+        .with_items(&[1])
+        .with_score(10);
+    player
+        .set_name("bob")
+        .set_items_inc(&[2])
+        .set_score_if(true, 20);
+    player.clear_items();
+    let player = player.without_score();
+    assert_eq!(player.name(), "bob");
+    assert!(player.items().is_empty());
+    assert_eq!(player.score(), None);
+}
+
+#[derive(Builder, Default)]
+#[args(toggle)]
+pub struct Features {
+    telemetry: bool,
+    cache: bool,
+    level: u8,
+}
+
+#[test]
+fn toggle() {
+    let features = Features::default()
+        .enable_telemetry()
+        .enable_cache()
+        .disable_cache()
+        .with_level(2);
+    assert!(features.telemetry());
+    assert!(!features.cache());
+}
+
+#[derive(Builder, Default)]
+#[builder(bool_getter = "is")]
+pub struct Widget {
+    visible: bool,
+    #[args(bool_getter = "has")]
+    focus: bool,
+    #[args(getter_prefix = "get")]
+    enabled: bool,
+    width: u32,
+}
+
+#[test]
+fn bool_getter() {
+    let widget = Widget::default()
+        .with_visible(true)
+        .with_focus(true)
+        .with_width(3);
+    assert!(widget.is_visible());
+    assert!(widget.has_focus());
+    assert!(!widget.get_enabled());
+    assert_eq!(widget.width(), 3);
+}
+
+#[derive(Builder, Default)]
+pub struct Playlist {
+    songs: Vec<String>,
+    ratings: Option<Vec<u8>>,
+}
+
+#[test]
+fn empty_slices_assign() {
+    let playlist = Playlist::default()
+        .with_songs(&["a"])
+        .with_ratings(&[5])
+        .with_songs(&[])
+        .with_ratings(&[]);
+    assert!(playlist.songs().is_empty());
+    assert_eq!(playlist.ratings(), Some(&[][..]));
+}
+
+pub struct Handle(pub std::fs::File);
+
+#[derive(Builder, Default)]
+pub struct Pool {
+    #[args(getter = false)]
+    handles: Vec<Handle>,
+    names: Vec<String>,
+}
+
+#[test]
+fn vec_owned() {
+    let names = vec![String::from("a"), String::from("b")];
+    let pool = Pool::default()
+        .with_handles_vec(Vec::new())
+        .with_names_vec(names);
+    assert_eq!(pool.names(), &["a", "b"]);
+}
+
+#[derive(Builder, Default)]
+pub struct Shared {
+    config: std::sync::Arc<Vec<u8>>,
+    label: std::rc::Rc<String>,
+    key: Option<std::sync::Arc<str>>,
+    path: std::sync::Arc<str>,
+    bytes: std::rc::Rc<[u8]>,
+}
+
+#[test]
+fn shared_inner() {
+    let shared = Shared::default()
+        .with_config_inner(vec![1, 2])
+        .with_label_inner("main")
+        .with_path(std::sync::Arc::from("/tmp"))
+        .with_bytes(std::rc::Rc::from([1u8, 2].as_slice()));
+    assert_eq!(shared.config().as_slice(), &[1, 2]);
+    assert_eq!(shared.label().as_str(), "main");
+    assert_eq!(&**shared.path(), "/tmp");
+    assert_eq!(&**shared.bytes(), &[1, 2]);
+    assert!(shared.key().is_none());
+    let config = shared.config().clone();
+    assert_eq!(shared.with_config(config).config().len(), 2);
+}
+
+#[derive(Builder)]
+pub struct Plugin {
+    hook: std::rc::Rc<dyn Fn(u32) -> u32>,
+}
+
+#[test]
+fn shared_dyn() {
+    let plugin = Plugin {
+        hook: std::rc::Rc::new(|x| x),
+    };
+    let plugin = plugin.with_hook(std::rc::Rc::new(|x| x + 1));
+    assert_eq!((plugin.hook())(1), 2);
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Tree {
+    value: u32,
+}
+
+#[derive(Builder, Default)]
+pub struct Node {
+    left: Option<Box<Tree>>,
+    callback: Option<Box<dyn Fn(u32) -> u32>>,
+    #[args(clamp = "..=10")]
+    weight: Option<Box<u32>>,
+}
+
+#[test]
+fn option_box() {
+    let node = Node::default()
+        .with_left(Tree { value: 1 })
+        .with_callback(Box::new(|x| x + 1));
+    assert_eq!(node.left(), Some(&Tree { value: 1 }));
+    assert_eq!(node.callback().map(|f| f(1)), Some(2));
+    let node = node.with_left_boxed(Box::new(Tree { value: 2 }));
+    assert_eq!(node.left().map(|x| x.value), Some(2));
+    assert_eq!(node.with_weight(20).weight(), Some(&10));
+}
+
+#[derive(Builder, Default)]
+pub struct Message<'a> {
+    body: std::borrow::Cow<'a, str>,
+    bytes: std::borrow::Cow<'a, [u8]>,
+}
+
+#[test]
+fn cow_str() {
+    let text = String::from("borrowed");
+    let message = Message::default().with_body(&text);
+    assert!(matches!(message.body, std::borrow::Cow::Borrowed(_)));
+    assert_eq!(message.body(), "borrowed");
+    let message = message
+        .with_body(format!("{}!", text))
+        .with_bytes(std::borrow::Cow::Borrowed(b"x"));
+    assert_eq!(message.body(), "borrowed!");
+    assert_eq!(message.bytes().as_ref(), b"x");
+}
+
+fn trim_cow(x: std::borrow::Cow<'_, str>) -> std::borrow::Cow<'_, str> {
+    match x {
+        std::borrow::Cow::Borrowed(x) => std::borrow::Cow::Borrowed(x.trim()),
+        std::borrow::Cow::Owned(x) => std::borrow::Cow::Owned(x.trim().to_string()),
+    }
+}
+
+#[derive(Builder, Default)]
+pub struct Headline<'a> {
+    #[args(map = "trim_cow")]
+    title: std::borrow::Cow<'a, str>,
+}
+
+#[test]
+fn cow_str_map() {
+    let headline = Headline::default().with_title(" news ");
+    assert!(matches!(headline.title, std::borrow::Cow::Borrowed("news")));
+    let headline = headline.with_title(String::from(" more "));
+    assert_eq!(headline.title(), "more");
+}
+
+#[derive(Builder, Default)]
+pub struct Memo {
+    hits: std::cell::Cell<u32>,
+    cache: std::cell::RefCell<Vec<String>>,
+}
+
+#[test]
+fn cells() {
+    let memo = Memo::default()
+        .with_hits(2)
+        .with_cache(vec!["a".to_string()]);
+    memo.cache().borrow_mut().push("b".into());
+    assert_eq!(memo.hits(), 2);
+    assert_eq!(memo.cache().borrow().len(), 2);
+}
+
+#[derive(Builder, Default)]
+pub struct State {
+    counter: std::sync::Arc<std::sync::Mutex<u32>>,
+    names: std::sync::Arc<std::sync::RwLock<Vec<String>>>,
+}
+
+#[test]
+fn shared_lock() {
+    let state = State::default()
+        .with_counter_inner(1)
+        .with_names_inner(vec!["a".to_string()]);
+    *state.counter().lock().unwrap() += 1;
+    assert_eq!(*state.counter().lock().unwrap(), 2);
+    assert_eq!(state.names().read().unwrap().len(), 1);
+    let counter = state.counter().clone();
+    let other = State::default().with_counter(counter);
+    assert_eq!(*other.counter().lock().unwrap(), 2);
+}
+
+#[derive(Builder)]
+pub struct Outcome {
+    status: Result<u16, String>,
+    io: std::io::Result<()>,
+}
+
+#[test]
+fn result_ok_err() {
+    let outcome = Outcome {
+        status: Ok(0),
+        io: Ok(()),
+    }
+    .with_status_ok(200);
+    assert_eq!(outcome.status(), &Ok(200));
+    let outcome = outcome.with_status_err("timeout".into());
+    assert_eq!(outcome.status(), &Err("timeout".to_string()));
+    assert_eq!(outcome.with_status(Ok(204)).status(), &Ok(204));
+}
+
+#[derive(Builder, Default)]
+pub struct Paths {
+    root: std::path::PathBuf,
+    cache: Option<std::path::PathBuf>,
+}
+
+#[test]
+fn path_buf() {
+    let paths = Paths::default()
+        .with_root("/srv")
+        .with_cache(std::path::Path::new("/tmp").join("aksr"));
+    assert_eq!(paths.root(), std::path::Path::new("/srv"));
+    assert_eq!(paths.cache(), Some(std::path::Path::new("/tmp/aksr")));
+    assert_eq!(paths.without_cache().cache(), None);
+}
+
+fn subdir(x: std::path::PathBuf) -> std::path::PathBuf {
+    x.join("x")
+}
+
+#[derive(Builder, Default)]
+pub struct Workspace {
+    #[args(map = "subdir")]
+    root: std::path::PathBuf,
+    #[args(map = "subdir")]
+    target: Option<std::path::PathBuf>,
+}
+
+#[test]
+fn path_buf_map() {
+    let workspace = Workspace::default().with_root("a").with_target("b");
+    assert_eq!(workspace.root(), std::path::Path::new("a/x"));
+    assert_eq!(workspace.target(), Some(std::path::Path::new("b/x")));
+}
+
+#[derive(Builder, Default)]
+pub struct Spawn {
+    program: std::ffi::OsString,
+    cwd: Option<std::ffi::OsString>,
+    symbol: std::ffi::CString,
+}
+
+#[test]
+fn os_and_c_strings() {
+    let spawn = Spawn::default()
+        .with_program("ls")
+        .with_cwd(std::path::Path::new("/tmp"))
+        .with_symbol(c"main");
+    assert_eq!(spawn.program(), std::ffi::OsStr::new("ls"));
+    assert_eq!(spawn.cwd(), Some(std::ffi::OsStr::new("/tmp")));
+    assert_eq!(spawn.symbol(), c"main");
+}
+
+fn quoted(x: std::ffi::OsString) -> std::ffi::OsString {
+    let mut quoted = std::ffi::OsString::from("'");
+    quoted.push(x);
+    quoted.push("'");
+    quoted
+}
+
+fn or_main(x: std::ffi::CString) -> std::ffi::CString {
+    match x.is_empty() {
+        true => c"main".to_owned(),
+        false => x,
+    }
+}
+
+#[derive(Builder, Default)]
+pub struct Exec {
+    #[args(map = "quoted")]
+    arg: std::ffi::OsString,
+    #[args(map = "quoted")]
+    env: Option<std::ffi::OsString>,
+    #[args(map = "or_main")]
+    entry: std::ffi::CString,
+}
+
+#[test]
+fn os_and_c_strings_map() {
+    let exec = Exec::default()
+        .with_arg("a b")
+        .with_env("x")
+        .with_entry(c"");
+    assert_eq!(exec.arg(), std::ffi::OsStr::new("'a b'"));
+    assert_eq!(exec.env(), Some(std::ffi::OsStr::new("'x'")));
+    assert_eq!(exec.entry(), c"main");
+}
+
+#[derive(Builder, Default)]
+pub struct Listen {
+    #[args(from_str)]
+    addr: Option<std::net::IpAddr>,
+    #[args(from_str)]
+    port: u16,
+}
+
+#[test]
+fn from_str() {
+    let listen = Listen::default()
+        .try_with_port_str("8080")
+        .unwrap()
+        .try_with_addr_str("127.0.0.1")
+        .unwrap();
+    assert_eq!(listen.port(), 8080);
+    assert_eq!(listen.addr(), Some(&std::net::IpAddr::from([127, 0, 0, 1])));
+    assert!(Listen::default().try_with_port_str("http").is_err());
+}
+
+#[derive(Builder, Default)]
+pub struct Packet {
+    payload: Vec<u8>,
+}
+
+#[test]
+fn bytes() {
+    let packet = Packet::default().with_payload(b"ping");
+    assert_eq!(packet.payload(), b"ping");
+    let packet = packet.with_payload("pong");
+    assert_eq!(packet.payload(), b"pong");
+    let packet = packet.with_payload(vec![1, 2]).with_payload([3_u8; 2]);
+    assert_eq!(packet.payload(), &[3, 3]);
+}
+
+#[derive(Builder, Default)]
+pub struct Mac {
+    octets: [u8; 6],
+    labels: [String; 2],
+}
+
+#[test]
+fn array_slice() {
+    let data = vec![0x00, 0x1b, 0x44, 0x11, 0x3a, 0xb7];
+    let mac = Mac::default().try_with_octets(&data).unwrap();
+    assert_eq!(mac.octets(), &[0x00, 0x1b, 0x44, 0x11, 0x3a, 0xb7]);
+    assert!(Mac::default().try_with_octets(&data[..4]).is_err());
+    let mac = mac.with_labels(["eth0".into(), "lan".into()]);
+    assert_eq!(mac.labels(), &["eth0", "lan"]);
+}
+
+#[derive(Builder)]
+pub struct Workers {
+    size: std::num::NonZeroUsize,
+    #[args(getter_prefix = "get")]
+    retries: std::num::NonZero<u8>,
+}
+
+#[test]
+fn non_zero() {
+    let workers = Workers {
+        size: std::num::NonZeroUsize::MIN,
+        retries: std::num::NonZero::<u8>::MIN,
+    };
+    let workers = workers
+        .try_with_size(8)
+        .unwrap()
+        .try_with_retries(3)
+        .unwrap();
+    assert_eq!((workers.size(), workers.get_retries()), (8, 3));
+    assert!(workers.try_with_size(0).is_err());
+}
+
+#[derive(Builder, Default)]
+pub struct Migration {
+    apply: bool,
+    version: u32,
+}
+
+#[test]
+fn apply_field() {
+    let migration = Migration::default().with_apply(true).with_version(2);
+    assert!(migration.apply());
+    assert_eq!(migration.version(), 2);
+}
+
+#[derive(Builder, Default)]
+pub struct Alarm {
+    when: u64,
+}
+
+#[derive(Builder, Default)]
+#[args(apply = false)]
+pub struct Lamp {
+    on: bool,
+}
+
+impl Lamp {
+    /// a user `apply`, the generated one is turned off
+    pub fn apply(self, on: bool) -> Self {
+        self.with_on(on)
+    }
+}
+
+#[derive(Builder, Default)]
+#[args(when = false)]
+pub struct Fan {
+    speed: u8,
+}
+
+impl Fan {
+    /// a user `when`, the generated one is turned off
+    pub fn when(&self) -> &'static str {
+        match self.speed {
+            0 => "never",
+            _ => "now",
+        }
+    }
+}
+
+#[test]
+fn apply_when_toggles() {
+    let lamp = Lamp::default().when(true, |x| x.apply(true));
+    assert!(lamp.on());
+    let fan = Fan::default().apply(|x| x.with_speed(2));
+    assert_eq!(fan.when(), "now");
+}
+
+#[test]
+fn when_field() {
+    let alarm = Alarm::default().with_when(10).apply(|x| x.with_when(20));
+    assert_eq!(alarm.when(), 20);
+}
+
+#[derive(Builder, Default)]
+pub struct Tally {
+    counter: std::sync::Mutex<u32>,
+}
+
+#[test]
+fn std_mutex() {
+    let tally = Tally::default().with_counter(std::sync::Mutex::new(3));
+    assert_eq!(*tally.counter().lock().unwrap(), 3);
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Vec2 {
+    u: f32,
+    v: f32,
+}
+
+#[derive(Builder, Default)]
+pub struct Uv {
+    coord: Vec2,
+}
+
+#[test]
+fn local_vec2() {
+    let uv = Uv::default().with_coord(Vec2 { u: 0.5, v: 1. });
+    assert_eq!(uv.coord(), &Vec2 { u: 0.5, v: 1. });
+}
@@ -72,3 +72,90 @@ fn all() {
         Some(&Some(vec!["string1".to_string(), "string2".to_string()]))
     );
 }
+
+#[test]
+fn strs() {
+    let entity = Entity::default().with_5(&["string1", "string2"]);
+    assert_eq!(
+        entity.nth_5_strs().collect::<Vec<_>>(),
+        ["string1", "string2"]
+    );
+}
+
+#[derive(Builder, Debug, Default)]
+#[args(view)]
+struct Record(usize, String, Vec<String>, Option<isize>);
+
+#[test]
+fn view() {
+    let record = Record::default().with_1("string").with_2(&["string1"]);
+    let view = record.view();
+    assert_eq!(view.0, 0);
+    assert_eq!(view.1, "string");
+    assert_eq!(view.2, &["string1".to_string()]);
+    assert_eq!(view.3, None);
+}
+
+#[derive(Builder, Debug, Default)]
+#[args(view)]
+struct Point(i32, i32);
+
+#[test]
+fn view_copy() {
+    let point = Point::default().with_0(1).with_1(2);
+    let view = point.view();
+    assert_eq!((view.0, view.1), (1, 2));
+}
+
+#[derive(Builder, Default)]
+#[args(field_id)]
+pub struct Pair(u8, #[args(alias = "second")] String);
+
+#[test]
+fn field_id() {
+    let pair = Pair::default().with_0(1).with_second("b");
+    assert!(matches!(pair.get(PairField::Nth0), PairFieldValue::Nth0(1)));
+    assert!(matches!(pair.get(PairField::Second), PairFieldValue::Second(x) if x == "b"));
+}
+
+#[derive(Builder, Default)]
+#[builder(getter_prefix = "get")]
+pub struct Span(usize, #[args(getter_prefix = "nth")] usize);
+
+#[test]
+fn struct_getter_prefix() {
+    let span = Span::default().with_0(1).with_1(2);
+    assert_eq!((span.get_0(), span.nth_1()), (1, 2));
+}
+
+#[derive(Builder)]
+pub struct Id(
+    #[args(required)] u64,
+    #[args(default = "anon")] &'static str,
+);
+
+#[test]
+fn required() {
+    let id = Id::new(7);
+    assert_eq!((id.nth_0(), id.nth_1()), (7, "anon"));
+}
+
+#[derive(Builder, Default)]
+pub struct Rgb(u8, u8, u8);
+
+#[test]
+fn apply() {
+    let gray = |x: Rgb| x.with_0(128).with_1(128).with_2(128);
+    let rgb = Rgb::default().apply(gray).with_2(255);
+    assert_eq!((rgb.nth_0(), rgb.nth_1(), rgb.nth_2()), (128, 128, 255));
+}
+
+#[test]
+fn when() {
+    let dark = true;
+    let rgb = Rgb::default()
+        .with_0(200)
+        .when(dark, |x| x.with_0(20).with_1(20))
+        .when(!dark, |x| x.with_2(200));
+    assert_eq!((rgb.nth_0(), rgb.nth_1(), rgb.nth_2()), (20, 20, 0));
+}